@@ -15,10 +15,14 @@ use crate::unrecoverable;
 
 // External library imports.
 use tephra::Context;
+use tephra::error::AmbiguousParseError;
+use tephra::error::ChoiceError;
 use tephra::Lexer;
+use tephra::ParseError;
 use tephra::ParseResult;
 use tephra::ParseResultExt as _;
 use tephra::Scanner;
+use tephra::Span;
 use tephra::Success;
 use tephra_tracing::event;
 use tephra_tracing::Level;
@@ -30,7 +34,8 @@ use tephra_tracing::span;
 // either
 ////////////////////////////////////////////////////////////////////////////////
 /// Returns a parser which attempts each of the given parsers in
-/// sequence, returning the first which succeeds.
+/// sequence, returning the first which succeeds. A committed failure of the
+/// left parser is returned without attempting the right parser.
 pub fn either<'text, Sc, L, R, X>(mut left: L, mut right: R)
     -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>
     where
@@ -42,17 +47,213 @@ pub fn either<'text, Sc, L, R, X>(mut left: L, mut right: R)
         let lexer_start = lexer.clone();
         
         (left)(lexer, ctx.clone())
-            .or_else(|_| (right)(lexer_start, ctx))
+            .or_else(|fail| if fail.is_committed() {
+                event!(Level::TRACE, "left parser failure committed");
+                Err(fail)
+            } else {
+                (right)(lexer_start, ctx)
+            })
 
         // TODO: Better error handling?
     }
 }
 
+/// A value produced by one of two parsers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+    /// The value produced by the left parser.
+    Left(L),
+    /// The value produced by the right parser.
+    Right(R),
+}
+
+/// Returns a parser which attempts each of the given parsers in sequence,
+/// returning the value of the first which succeeds tagged with which parser
+/// produced it.
+///
+/// If both parsers fail, the failure which progressed furthest into the text
+/// is returned, preferring the failure of the left parser. A committed failure
+/// of the left parser is returned without attempting the right parser.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn either_of<'text, Sc, L, R, X, Y>(mut left: L, mut right: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Either<X, Y>>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+{
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "either_of").entered();
+
+        let left_fail = match (left)(lexer.clone(), ctx.clone()) {
+            Ok(succ) => return Ok(succ.map_value(Either::Left)),
+            Err(fail) if fail.is_committed() => {
+                event!(Level::TRACE, "left parser failure committed");
+                return Err(fail);
+            },
+            Err(fail) => fail,
+        };
+        let right_fail = match (right)(lexer, ctx) {
+            Ok(succ) => return Ok(succ.map_value(Either::Right)),
+            Err(fail) => fail,
+        };
+
+        let progress = |fail: &dyn ParseError| fail
+            .error_span()
+            .map(|span| span.end().byte);
+        if progress(&*right_fail) > progress(&*left_fail) {
+            event!(Level::TRACE, "right parser failed further");
+            Err(right_fail)
+        } else {
+            Err(left_fail)
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// choice
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a parser which attempts each of the given parsers in sequence from
+/// the same position, returning the first which succeeds.
+///
+/// If every parser fails, a `ChoiceError` is returned spanning the attempted
+/// alternatives and noting the failure of each. A committed failure is
+/// returned without attempting the remaining parsers.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn choice<'text: 'a, 'a, Sc, P, V: 'a>(parsers: &'a mut [P])
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, V> + 'a
+    where
+        Sc: Scanner + 'a,
+        P: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, V> + 'a,
+{
+    assert!(!parsers.is_empty(), "empty parser slice not supported");
+
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "choice").entered();
+
+        let mut error_span = Span::at(lexer.cursor_pos());
+        let mut failures = Vec::with_capacity(parsers.len());
+        for (index, parser) in parsers.iter_mut().enumerate() {
+            match (parser)(lexer.clone(), ctx.clone()) {
+                Ok(succ) => {
+                    event!(Level::TRACE, "alternative {} matched", index);
+                    return Ok(succ);
+                },
+                Err(fail) if fail.is_committed() => {
+                    event!(Level::TRACE, "alternative {} committed", index);
+                    return Err(fail);
+                },
+                Err(fail) => {
+                    if let Some(span) = fail.error_span() {
+                        error_span = error_span.enclose(span);
+                    }
+                    failures.push(fail);
+                },
+            }
+        }
+
+        event!(Level::DEBUG, "ChoiceError ({} alternatives)", failures.len());
+        Err(Box::new(ChoiceError { error_span, failures }))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// unambiguous
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a parser which attempts every one of the given parsers from the
+/// same position, requiring exactly one of them to succeed.
+///
+/// If more than one parser succeeds, an `AmbiguousParseError` is emitted
+/// noting the span matched by each succeeding parser. If all parsers fail, the
+/// failure of the first parser is returned.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted within the given parsers. The error sink is
+/// restored once every parser has been attempted, so enclosing recovery
+/// combinators are unaffected. If the `AmbiguousParseError` is recovered by
+/// the `Context`, the result of the first succeeding parser is returned.
+pub fn unambiguous<'text: 'a, 'a, Sc, P, V: 'a>(parsers: &'a mut [P])
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, V> + 'a
+    where
+        Sc: Scanner + 'a,
+        P: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, V> + 'a,
+{
+    assert!(!parsers.is_empty(), "empty parser slice not supported");
+
+    move |lexer, mut ctx| {
+        let _trace_span = span!(Level::DEBUG, "unambiguous").entered();
+
+        let error_sink = ctx.take_error_sink();
+        event!(Level::TRACE, "error recovery disabled");
+
+        let mut first_success = None;
+        let mut first_failure = None;
+        let mut matches = Vec::new();
+        for (index, parser) in parsers.iter_mut().enumerate() {
+            match (parser)(lexer.clone(), ctx.clone()) {
+                Ok(succ) => {
+                    event!(Level::TRACE, "interpretation {} matched", index);
+                    matches.push((index, Span::enclosing(
+                        lexer.cursor_pos(),
+                        succ.lexer.cursor_pos())));
+                    if first_success.is_none() {
+                        first_success = Some(succ);
+                    }
+                },
+                Err(fail) => if first_failure.is_none() {
+                    first_failure = Some(fail);
+                },
+            }
+        }
+
+        if let Some(error_sink) = error_sink {
+            let _ = ctx.replace_error_sink(error_sink);
+            event!(Level::TRACE, "error recovery restored");
+        }
+
+        let Some(succ) = first_success else {
+            return Err(first_failure
+                .expect("failure for each attempted parser"));
+        };
+        if matches.len() == 1 { return Ok(succ); }
+
+        event!(Level::DEBUG, "AmbiguousParseError ({} matches)",
+            matches.len());
+        let error_span = matches
+            .iter()
+            .fold(matches[0].1, |span, (_, matched)| span.enclose(*matched));
+        let parse_error = Box::new(AmbiguousParseError {
+            error_span,
+            matches,
+        });
+
+        match ctx.send_error(parse_error) {
+            Err(parse_error) => Err(parse_error),
+            Ok(()) => Ok(succ),
+        }
+    }
+}
+
 
 ////////////////////////////////////////////////////////////////////////////////
 // maybe
 ////////////////////////////////////////////////////////////////////////////////
 /// Returns a parser which converts any failure into an empty success.
+/// Committed failures are returned rather than converted.
 pub fn maybe<'text, Sc, F, V>(mut parser: F)
     -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
         -> ParseResult<'text, Sc, Option<V>>
@@ -69,6 +270,10 @@ pub fn maybe<'text, Sc, F, V>(mut parser: F)
                     value: Some(succ.value),
                 })
             },
+            Err(fail) if fail.is_committed() => {
+                event!(Level::TRACE, "maybe failure committed");
+                Err(fail)
+            },
             Err(_e) => {
                 event!(Level::TRACE, "maybe None: ({})", _e);
                 Ok(Success {
@@ -10,6 +10,7 @@
 
 // Internal library imports.
 use crate::map;
+use crate::one;
 
 // External library imports.
 use smallvec::SmallVec;
@@ -208,6 +209,110 @@ pub fn bracket_default_index<'text: 'a, 'a, Sc, F, X: 'a, A>(
     }
 }
 
+/// Returns a parser which parses the given content, optionally wrapped in any
+/// number of nested `open` and `close` token pairs, returning the content's
+/// value in either case.
+///
+/// If the next token is an `open` token, the parenthesized form is attempted
+/// first on a copy of the lexer, and the content is then parsed without
+/// brackets. Whichever succeeds and advances further is returned, so the
+/// content parser may itself begin with an `open` token, and content such as
+/// `(a) + (b)` is not mistaken for a parenthesized `a`. If both forms fail,
+/// the failure of the parenthesized form is returned.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted. Committed failures from either form are
+/// returned immediately.
+pub fn maybe_parenthesized<'text, Sc, F, X>(
+    open_token: Sc::Token,
+    mut content: F,
+    close_token: Sc::Token)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, X>
+    where
+        Sc: Scanner,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, X>,
+{
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "maybe_parenthesized").entered();
+
+        parenthesized_or_bare(
+            &open_token,
+            &mut content,
+            &close_token,
+            lexer,
+            ctx)
+    }
+}
+
+/// Parses the content of `maybe_parenthesized` at a single bracket depth.
+fn parenthesized_or_bare<'text, Sc, F, X>(
+    open_token: &Sc::Token,
+    content: &mut F,
+    close_token: &Sc::Token,
+    mut lexer: Lexer<'text, Sc>,
+    ctx: Context<'text, Sc>)
+    -> ParseResult<'text, Sc, X>
+    where
+        Sc: Scanner,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, X>,
+{
+    if lexer.peek().as_ref() != Some(open_token) {
+        return (content)(lexer, ctx);
+    }
+
+    let parenthesized = one(open_token.clone())
+        (lexer.clone(), ctx.clone())
+        .and_then(|succ| parenthesized_or_bare(
+            open_token,
+            content,
+            close_token,
+            succ.lexer,
+            ctx.clone()))
+        .and_then(|succ| {
+            let (value, succ) = succ.take_value();
+            one(close_token.clone())
+                (succ.lexer, ctx.clone())
+                .map(|succ| succ.map_value(|_| value))
+        });
+
+    match parenthesized {
+        Err(fail) if fail.is_committed() => {
+            event!(Level::TRACE, "parenthesized failure committed");
+            Err(fail)
+        },
+
+        Ok(paren_succ) => match (content)(lexer, ctx) {
+            Ok(succ) if succ.lexer.cursor_pos().byte
+                > paren_succ.lexer.cursor_pos().byte =>
+            {
+                event!(Level::TRACE, "bare content advanced further");
+                Ok(succ)
+            },
+            Err(fail) if fail.is_committed() => {
+                event!(Level::TRACE, "bare content failure committed");
+                Err(fail)
+            },
+            _ => Ok(paren_succ),
+        },
+
+        Err(paren_fail) => match (content)(lexer, ctx) {
+            Ok(succ) => {
+                event!(Level::TRACE, "falling back to bare content");
+                Ok(succ)
+            },
+            Err(fail) if fail.is_committed() => {
+                event!(Level::TRACE, "bare content failure committed");
+                Err(fail)
+            },
+            Err(_) => Err(paren_fail),
+        },
+    }
+}
+
 /// Returns a `BracketMatch` object where the open lexer's `next` token is in
 /// `open_tokens` and the close lexer's `next` token is the corresponding entry
 /// in `right_tokens`.
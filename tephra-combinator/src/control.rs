@@ -10,8 +10,12 @@
 
 
 // External library imports.
+use tephra::CommitPolicy;
+use tephra::error::CommittedError;
+use tephra::error::UnrecoverableError;
 use tephra::Context;
 use tephra::Lexer;
+use tephra::ParseError;
 use tephra::ParseResult;
 use tephra::ParseResultExt as _;
 use tephra::Recover;
@@ -51,6 +55,14 @@ pub fn raw<'text, Sc, F, V>(mut parser: F)
 }
 
 /// A combinator which disables error recovery.
+///
+/// Error recovery is disabled within the given parser, and re-enabled once it
+/// completes. Any failure produced by the given parser is committed. How a
+/// committed failure is handled depends on the `Context`'s `CommitPolicy`:
+/// with `CommitPolicy::Abort`, the failure is made unrecoverable, so enclosing
+/// recovery combinators such as `recover` and `recover_default` will propagate
+/// it to the caller. With `CommitPolicy::Collect`, enclosing recovery
+/// combinators may recover from it.
 pub fn unrecoverable<'text, Sc, F, V>(mut parser: F)
     -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
         -> ParseResult<'text, Sc, V>
@@ -63,16 +75,32 @@ pub fn unrecoverable<'text, Sc, F, V>(mut parser: F)
         let _trace_span = span!(Level::DEBUG, "~unrec").entered();
 
         let mut ctx = ctx.clone();
-        let _ = ctx.take_error_sink();
+        let error_sink = ctx.take_error_sink();
         event!(Level::TRACE, "error recovery disabled");
         
-        (parser)
-            (lexer, ctx)
+        let res = (parser)
+            (lexer, ctx.clone());
+
+        if let Some(error_sink) = error_sink {
+            let _ = ctx.replace_error_sink(error_sink);
+            event!(Level::TRACE, "error recovery restored");
+        }
+
+        match (res, ctx.commit_policy()) {
+            (Err(fail), CommitPolicy::Abort) if fail.is_recoverable() => {
+                Err(Box::new(UnrecoverableError::new(fail)))
+            },
+            (res, _) => res,
+        }
     }
 }
 
 /// A combinator which performs error recovery, returning a `None` value when
 /// an error occurs.
+///
+/// Only recoverable failures are recovered. Failures which are not
+/// recoverable, or which were committed by an `unrecoverable` parser, are
+/// propagated.
 pub fn recover<'text, Sc, F, V>(
     mut parser: F,
     recover: Recover<Sc::Token>)
@@ -136,6 +164,10 @@ pub fn recover_default_delayed<'text, Sc, F, V>(
 
 /// A combinator which performs error recovery, returning a default value when
 /// an error occurs.
+///
+/// Only recoverable failures are recovered. Failures which are not
+/// recoverable, or which were committed by an `unrecoverable` parser, are
+/// propagated.
 pub fn recover_default<'text, Sc, F, V>(
     mut parser: F,
     recover: Recover<Sc::Token>)
@@ -168,6 +200,7 @@ pub fn recover_default<'text, Sc, F, V>(
                     Ok(_) => {
                         event!(Level::DEBUG, "error recovery point found ({})",
                             base_lexer.cursor_pos());
+                        ctx.report_progress(base_lexer.cursor_pos());
                         Ok(Success {
                             lexer: base_lexer,
                             value: V::default(),
@@ -191,6 +224,141 @@ pub fn recover_default<'text, Sc, F, V>(
     }
 }
 
+/// A combinator which commits to a parse once the given `prefix` parse
+/// succeeds.
+///
+/// If the `prefix` parse fails, the failure is returned as-is, allowing
+/// alternative parses to be attempted. Once the `prefix` parse succeeds, any
+/// failure of the `rest` parse is committed, so enclosing alternative
+/// combinators such as `either` and `maybe` will return it rather than
+/// attempting another alternative.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn commit_after<'text, Sc, P, R, X, Y>(mut prefix: P, mut rest: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, (X, Y)>
+    where
+        Sc: Scanner,
+        P: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, X>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, Y>,
+{
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "commit_after").entered();
+
+        let (pre, succ) = (prefix)
+            (lexer, ctx.clone())?
+            .take_value();
+        event!(Level::TRACE, "parse committed");
+
+        match (rest)(succ.lexer, ctx) {
+            Ok(succ) => Ok(succ.map_value(|r| (pre, r))),
+            Err(fail) if fail.is_committed() => Err(fail),
+            Err(fail) => Err(Box::new(CommittedError::new(fail))),
+        }
+    }
+}
+
+/// A combinator which delimits a section of the parse, entered by a unique
+/// `prefix` parse. The section acts as a boundary for error collection and
+/// parse continuation.
+///
+/// If the `prefix` parse fails, the failure is returned and the section is not
+/// entered, allowing alternative parses to be attempted. Once the `prefix`
+/// parse succeeds, the section is committed: error recovery is disabled within
+/// the `body` parse, so any failure within it, including lexer errors, is
+/// subsumed by the first failure to reach the section boundary. Any failure
+/// returned after the section is entered is committed, as with
+/// `commit_after`.
+///
+/// ## Error recovery
+///
+/// A recoverable failure of the `body` parse is sent to the `Context`, and the
+/// parse continues from the recovery point with a `None` value. Unlike
+/// `unrecoverable`, the error sink is always restored once the `body` parse
+/// completes and the failure remains recoverable, regardless of the
+/// `CommitPolicy`, as the section boundary is itself a point of recovery.
+pub fn section<'text, Sc, P, B, X, Y>(
+    mut prefix: P,
+    mut body: B,
+    recover: Recover<Sc::Token>)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Option<(X, Y)>>
+    where
+        Sc: Scanner,
+        P: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, X>,
+        B: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, Y>,
+{
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "section").entered();
+
+        let (pre, succ) = (prefix)
+            (lexer, ctx.clone())?
+            .take_value();
+        event!(Level::TRACE, "section entered");
+
+        let mut base_lexer = succ.lexer.clone();
+        base_lexer.set_recover_state(Some(Rc::clone(&recover)));
+
+        let mut body_ctx = ctx.clone();
+        let error_sink = body_ctx.take_error_sink();
+        let res = (body)
+            (succ.lexer, body_ctx.clone());
+        if let Some(error_sink) = error_sink {
+            let _ = body_ctx.replace_error_sink(error_sink);
+        }
+
+        let commit = |fail: Box<dyn ParseError>| -> Box<dyn ParseError> {
+            if fail.is_committed() {
+                fail
+            } else {
+                Box::new(CommittedError::new(fail))
+            }
+        };
+
+        match res {
+            Ok(succ) => Ok(succ.map_value(|b| Some((pre, b)))),
+
+            Err(fail) if fail.is_recoverable() => match ctx.send_error(fail) {
+                Err(fail) => {
+                    event!(Level::TRACE, "section recovery failed: disabled");
+                    Err(commit(fail))
+                },
+
+                Ok(()) => match base_lexer.advance_to_recover() {
+                    Ok(_) => {
+                        event!(Level::DEBUG, "section recovery point found \
+                            ({})",
+                            base_lexer.cursor_pos());
+                        ctx.report_progress(base_lexer.cursor_pos());
+                        Ok(Success {
+                            lexer: base_lexer,
+                            value: None,
+                        })
+                    },
+                    Err(recover_error) => {
+                        event!(Level::DEBUG, "section recovery failed: \
+                            unable to find recovery point ({})",
+                            base_lexer.cursor_pos());
+                        Err(commit(Box::new(recover_error)))
+                    },
+                },
+            },
+
+            Err(fail) => {
+                event!(Level::DEBUG, "section recovery failed: unrecoverable \
+                    error type");
+                Err(commit(fail))
+            },
+        }
+    }
+}
+
 /// A combinator which ends error recovery if a successful parse is achieved, or
 /// resumes error recovery if a failure occurs.
 pub fn stabilize<'text, Sc, F, V>(mut parser: F)
@@ -222,6 +390,7 @@ pub fn stabilize<'text, Sc, F, V>(mut parser: F)
                             event!(Level::DEBUG, "error recovery point found \
                                 ({})",
                                 lexer.cursor_pos());
+                            ctx.report_progress(lexer.cursor_pos());
 
                             res = unrecoverable(&mut parser)
                                 (lexer.clone(), ctx.clone());
@@ -271,14 +440,14 @@ pub fn filter_with<'text, Sc, F, P, V>(filter: F, mut parser: P)
     move |mut lexer, ctx| {
         let _trace_span = span!(Level::TRACE, "~filt_").entered();
 
-        let old_filter = lexer.set_filter(Some(Rc::clone(&filter)));
+        lexer.push_filter(Some(Rc::clone(&filter)));
         event!(Level::TRACE, "new lexer filter applied");
 
         (parser)
             (lexer, ctx)
             .map(|mut succ| {
                 event!(Level::TRACE, "lexer filter restored");
-                let _ = succ.lexer.set_filter(old_filter);
+                let _ = succ.lexer.pop_filter();
                 succ
             })
     }
@@ -304,14 +473,14 @@ pub fn unfiltered<'text, Sc, F, V>(mut parser: F)
     move |mut lexer, ctx| {
         let _trace_span = span!(Level::TRACE, "~unfilt").entered();
 
-        let filter = lexer.set_filter(None);
+        lexer.push_filter(None);
         event!(Level::TRACE, "lexer filter disabled");
 
         (parser)
             (lexer, ctx)
             .map(|mut succ| {
                 event!(Level::TRACE, "lexer filter enabled");
-                let _ = succ.lexer.set_filter(filter);
+                let _ = succ.lexer.pop_filter();
                 succ
             })
     }
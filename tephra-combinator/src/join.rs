@@ -10,6 +10,8 @@
 
 // Internal library imports.
 use crate::map;
+use crate::one;
+use crate::spanned;
 
 // External library imports.
 use tephra::Context;
@@ -17,6 +19,8 @@ use tephra::Lexer;
 use tephra::ParseResult;
 use tephra::ParseResultExt as _;
 use tephra::Scanner;
+use tephra::Spanned;
+use tephra_tracing::event;
 use tephra_tracing::Level;
 use tephra_tracing::span;
 
@@ -28,6 +32,10 @@ use tephra_tracing::span;
 /// Returns a parser which sequences two parsers which must both succeed,
 /// returning the value of the first one.
 ///
+/// If the first parser fails, the second parser is not attempted, and the
+/// failure is returned unchanged. No error context is applied, so failures
+/// committed by an `unrecoverable` parser pass through as-is.
+///
 /// ## Error recovery
 ///
 /// No error recovery is attempted.
@@ -46,6 +54,14 @@ pub fn left<'text, Sc, L, R, X, Y>(mut left: L, mut right: R)
 
 /// Returns a parser which sequences two parsers which must both succeed,
 /// returning the value of the second one.
+///
+/// If the first parser fails, the second parser is not attempted, and the
+/// failure is returned unchanged. No error context is applied, so failures
+/// committed by an `unrecoverable` parser pass through as-is.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
 pub fn right<'text, Sc, L, R, X, Y>(mut left: L, mut right: R)
     -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>
     where
@@ -62,6 +78,9 @@ pub fn right<'text, Sc, L, R, X, Y>(mut left: L, mut right: R)
 /// Returns a parser which sequences two parsers which must both succeed,
 /// returning their values in a tuple.
 ///
+/// If the first parser fails, the second parser is not attempted, and the
+/// failure is returned unchanged.
+///
 /// ## Error recovery
 ///
 /// No error recovery is attempted.
@@ -87,6 +106,93 @@ pub fn both<'text, Sc, L, R, X, Y>(mut left: L, mut right: R)
     }
 }
 
+/// Returns a parser which sequences two parsers separated by the given token,
+/// which must all succeed, returning the values of the parsers in a tuple.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn both_sep<'text, Sc, L, R, X, Y>(
+    mut left: L,
+    sep_token: Sc::Token,
+    mut right: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, (X, Y)>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+{
+    move |lexer, ctx| {
+        map(
+            both(
+                both(&mut left, one(sep_token.clone())),
+                &mut right),
+            |((l, _), r)| (l, r))
+            (lexer, ctx)
+    }
+}
+
+/// Returns a parser which sequences two parsers which must both succeed,
+/// returning their values in a tuple together with the span enclosing both
+/// parses.
+///
+/// This is equivalent to `spanned(both(L, R))`.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn both_spanned<'text, Sc, L, R, X, Y>(left: L, right: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<(X, Y)>>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+{
+    spanned(both(left, right))
+}
+
+/// Returns a parser which sequences two parsers which must both succeed,
+/// returning the value of the first one together with the span enclosing both
+/// parses.
+///
+/// This is equivalent to `spanned(left(L, R))`.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn left_spanned<'text, Sc, L, R, X, Y>(l: L, r: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<X>>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+{
+    spanned(left(l, r))
+}
+
+/// Returns a parser which sequences two parsers which must both succeed,
+/// returning the value of the second one together with the span enclosing
+/// both parses.
+///
+/// This is equivalent to `spanned(right(L, R))`.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn right_spanned<'text, Sc, L, R, X, Y>(l: L, r: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<Y>>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+{
+    spanned(right(l, r))
+}
+
 /// Returns a parser which sequences three parsers which must all succeed,
 /// returning the value of the center parser.
 ///
@@ -130,3 +236,186 @@ pub fn center<'text, Sc, L, C, R, X, Y, Z>(
     }
 }
 
+/// Returns a parser which sequences three parsers which must all succeed,
+/// returning the value of the center parser.
+///
+/// This is equivalent to `center(open, inner, close)`.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn delimited<'text, Sc, L, C, R, X, Y, Z>(open: L, inner: C, close: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        C: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Z>,
+{
+    center(open, inner, close)
+}
+
+/// Returns a parser which sequences three parsers which must all succeed,
+/// returning the value of the center parser together with the span enclosing
+/// all three parses.
+///
+/// This is equivalent to `spanned(center(L, C, R))`.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn center_spanned<'text, Sc, L, C, R, X, Y, Z>(l: L, c: C, r: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<Y>>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        C: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Z>,
+{
+    spanned(center(l, c, r))
+}
+
+/// Returns a parser which sequences three parsers which must all succeed,
+/// returning the values of the first and last parsers in a tuple.
+///
+/// This is equivalent to `both(left(first, sep), second)`.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn separated_pair<'text, Sc, L, C, R, X, Y, Z>(
+    first: L,
+    sep: C,
+    second: R)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, (X, Z)>
+    where
+        Sc: Scanner,
+        L: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        C: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+        R: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Z>,
+{
+    both(left(first, sep), second)
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Folding combinators.
+////////////////////////////////////////////////////////////////////////////////
+
+/// Returns a parser which parses a sequence of operands separated by
+/// operators, folding them together left-associatively. For operands `a`, `b`,
+/// `c`, the parsed value is `fold_fn(fold_fn(a, op, b), op, c)`.
+///
+/// If no operator follows the first operand, the first operand's value is
+/// returned. If an operator is not followed by an operand, the operand's
+/// failure is returned. A committed failure of the operator is returned rather
+/// than ending the sequence.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn fold_left<'text, Sc, P, O, F, X, Y>(
+    mut operand: P,
+    mut operator: O,
+    mut fold_fn: F)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, X>
+    where
+        Sc: Scanner,
+        P: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        O: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+        F: FnMut(X, Y, X) -> X,
+{
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "fold_left").entered();
+
+        let (mut acc, mut succ) = (operand)
+            (lexer, ctx.clone())?
+            .take_value();
+
+        loop {
+            let (op, next) = match (operator)
+                (succ.lexer.clone(), ctx.clone())
+            {
+                Ok(next) => next.take_value(),
+                Err(fail) if fail.is_committed() => {
+                    event!(Level::TRACE, "operator failure committed");
+                    return Err(fail);
+                },
+                Err(_) => break,
+            };
+
+            let (rhs, next) = (operand)
+                (next.lexer, ctx.clone())?
+                .take_value();
+            acc = (fold_fn)(acc, op, rhs);
+            succ = next;
+        }
+
+        Ok(succ.map_value(|_| acc))
+    }
+}
+
+/// Returns a parser which parses a sequence of operands separated by
+/// operators, folding them together right-associatively. For operands `a`,
+/// `b`, `c`, the parsed value is `fold_fn(a, op, fold_fn(b, op, c))`.
+///
+/// If no operator follows the first operand, the first operand's value is
+/// returned. If an operator is not followed by an operand, the operand's
+/// failure is returned. A committed failure of the operator is returned rather
+/// than ending the sequence.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn fold_right<'text, Sc, P, O, F, X, Y>(
+    mut operand: P,
+    mut operator: O,
+    mut fold_fn: F)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, X>
+    where
+        Sc: Scanner,
+        P: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, X>,
+        O: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, Y>,
+        F: FnMut(X, Y, X) -> X,
+{
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "fold_right").entered();
+
+        let (mut last, mut succ) = (operand)
+            (lexer, ctx.clone())?
+            .take_value();
+
+        // Collect each operand with its following operator.
+        let mut pending = Vec::new();
+        loop {
+            let (op, next) = match (operator)
+                (succ.lexer.clone(), ctx.clone())
+            {
+                Ok(next) => next.take_value(),
+                Err(fail) if fail.is_committed() => {
+                    event!(Level::TRACE, "operator failure committed");
+                    return Err(fail);
+                },
+                Err(_) => break,
+            };
+
+            let (rhs, next) = (operand)
+                (next.lexer, ctx.clone())?
+                .take_value();
+            pending.push((std::mem::replace(&mut last, rhs), op));
+            succ = next;
+        }
+
+        let mut acc = last;
+        while let Some((lhs, op)) = pending.pop() {
+            acc = (fold_fn)(lhs, op, acc);
+        }
+
+        Ok(succ.map_value(|_| acc))
+    }
+}
@@ -14,10 +14,12 @@ use crate::empty;
 use crate::discard;
 use crate::one;
 use crate::recover_default;
+use crate::spanned;
 use crate::stabilize;
 use crate::maybe;
 
 // External library imports.
+use tephra::error::EmptyListError;
 use tephra::error::ParseBoundaryError;
 use tephra::error::RepeatCountError;
 use tephra::Context;
@@ -25,6 +27,7 @@ use tephra::Lexer;
 use tephra::ParseResult;
 use tephra::ParseResultExt as _;
 use tephra::Scanner;
+use tephra::Spanned;
 use tephra::Success;
 use tephra_tracing::event;
 use tephra_tracing::Level;
@@ -99,6 +102,72 @@ pub fn list<'text: 'a, 'a, Sc, F, X: 'a, A>(
 }
 
 
+/// Returns a parser which parses a list of items separated by `sep_token`,
+/// together with the span enclosing the list. The span of an empty list is
+/// the empty span at the position where the list was expected.
+///
+/// This is equivalent to `spanned(list(...))`.
+///
+/// ## Error recovery
+///
+/// Item errors are recovered as in `list`.
+pub fn list_spanned<'text: 'a, 'a, Sc, F, X: 'a, A>(
+    parser: F,
+    sep_token: Sc::Token,
+    abort_pred: A)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<Vec<Option<X>>>> + 'a
+    where
+        Sc: Scanner + 'a,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, X> + 'a,
+        A: Fn(&Sc::Token) -> bool + 'static + Clone,
+{
+    spanned(list(parser, sep_token, abort_pred))
+}
+
+/// Returns a parser which parses a list of items separated by `sep_token`,
+/// requiring at least one item to be present.
+///
+/// This behaves like `list`, except that an empty list is an error.
+///
+/// ## Error recovery
+///
+/// Item errors are recovered as in `list`. If no items are found, an
+/// `EmptyListError` is emitted and an empty list will be returned.
+pub fn separated_nonempty_list<'text: 'a, 'a, Sc, F, X: 'a, A>(
+    parser: F,
+    sep_token: Sc::Token,
+    abort_pred: A)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Vec<Option<X>>> + 'a
+    where
+        Sc: Scanner + 'a,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, X> + 'a,
+        A: Fn(&Sc::Token) -> bool + 'static + Clone,
+{
+    let mut list_parser = list(parser, sep_token, abort_pred);
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "nonempty_list").entered();
+
+        let succ = (list_parser)(lexer, ctx.clone())?;
+        if !succ.value.is_empty() {
+            return Ok(succ);
+        }
+
+        event!(Level::DEBUG, "empty list");
+        let parse_error = Box::new(EmptyListError {
+            error_span: succ.lexer.parse_span(),
+        });
+        match ctx.send_error(parse_error) {
+            Err(parse_error) => Err(parse_error),
+            Ok(()) => Ok(succ),
+        }
+    }
+}
+
+
 pub fn list_bounded<'text: 'a, 'a, Sc, F, X: 'a, A>(
     low: usize,
     high: Option<usize>,
@@ -168,8 +237,8 @@ pub fn list_bounded_default<'text: 'a, 'a, Sc, F, X: 'a, A>(
 
     let rec_token = sep_token.clone();
     let rec_pred = abort_pred.clone();
-    let recover_pat = Rc::new(RwLock::new(move |tok: Sc::Token| {
-        Ok(tok == rec_token || rec_pred(&tok))
+    let recover_pat = Rc::new(RwLock::new(move |tok: Option<Sc::Token>| {
+        Ok(tok.is_some_and(|tok| tok == rec_token || rec_pred(&tok)))
     }));
     move |mut lexer, ctx| {
         let _trace_span = span!(Level::DEBUG, "list_*").entered();
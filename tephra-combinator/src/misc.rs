@@ -165,3 +165,51 @@ pub fn spanned<'text, Sc, F, V>(mut parser: F)
         }
     }
 }
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Memoization combinators.
+////////////////////////////////////////////////////////////////////////////////
+
+/// A combinator which memoizes successful parse results on the `Context`,
+/// keyed by the given `key` and the start byte of the parse. At most
+/// `capacity` results are retained for the key; the least recently used
+/// results are evicted first.
+///
+/// Only the parsed value and the lexer position and scanner state following
+/// the parse are memoized. A memoized result is applied to the given lexer,
+/// so its filters and other settings are retained.
+///
+/// The `key` should uniquely identify the parser and any lexer state (such as
+/// the active filter) which affects its result, as memoized results are shared
+/// by all `memoize_bounded` parsers using the same key and `Context`.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted. Failed parses are not memoized.
+pub fn memoize_bounded<'text, Sc, F, V>(
+    capacity: usize,
+    key: &'static str,
+    mut parser: F)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, V>
+    where
+        Sc: Scanner,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, V>,
+        V: Clone + 'static,
+{
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "memoize_bounded").entered();
+        let byte = lexer.cursor_pos().byte;
+
+        if let Some((checkpoint, value)) = ctx.memo_get(key, byte) {
+            lexer.advance_to_checkpoint(checkpoint);
+            return Ok(Success { lexer, value });
+        }
+
+        let succ = (parser)(lexer, ctx.clone())?;
+        ctx.memo_insert(capacity, key, byte, &succ);
+        Ok(succ)
+    }
+}
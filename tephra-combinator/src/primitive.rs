@@ -9,10 +9,13 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Internal library imports.
+use crate::spanned;
 use tephra::Context;
 use tephra::Lexer;
 use tephra::ParseResult;
 use tephra::Scanner;
+use tephra::Span;
+use tephra::Spanned;
 use tephra::Success;
 
 // External library imports.
@@ -20,7 +23,9 @@ use simple_predicates::DnfVec;
 use simple_predicates::Eval;
 use simple_predicates::Expr;
 use tephra::error::Expected;
+use tephra::error::FailureError;
 use tephra::error::Found;
+use tephra::error::MissingTokenError;
 use tephra::error::UnexpectedTokenError;
 use tephra::error::UnrecognizedTokenError;
 use tephra_tracing::event;
@@ -57,6 +62,61 @@ pub fn empty<'text, Sc>(lexer: Lexer<'text, Sc>, _ctx: Context<'text, Sc>)
     })
 }
 
+
+////////////////////////////////////////////////////////////////////////////////
+// fail
+////////////////////////////////////////////////////////////////////////////////
+/// Returns a parser which always fails with a `FailureError` with the given
+/// message at the current position.
+///
+/// This is useful as the final alternative of a choice, to report a more
+/// specific error than the failure of any individual alternative.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted. The returned error is recoverable.
+pub fn fail<'text, Sc, V, M>(message: M)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, V>
+    where
+        Sc: Scanner,
+        M: Into<String>,
+{
+    let message = message.into();
+    move |lexer, _ctx| {
+        let _trace_span = span!(Level::TRACE, "fail").entered();
+        event!(Level::TRACE, "FailureError ({})", message);
+        Err(Box::new(FailureError {
+            error_span: Span::at(lexer.cursor_pos()),
+            message: message.clone(),
+        }))
+    }
+}
+
+/// Returns a parser which always fails with a `FailureError` with the given
+/// message at the given span.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted. The returned error is recoverable.
+pub fn fail_at<'text, Sc, V, M>(span: Span, message: M)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, V>
+    where
+        Sc: Scanner,
+        M: Into<String>,
+{
+    let message = message.into();
+    move |_lexer, _ctx| {
+        let _trace_span = span!(Level::TRACE, "fail_at").entered();
+        event!(Level::TRACE, "FailureError ({})", message);
+        Err(Box::new(FailureError {
+            error_span: span,
+            message: message.clone(),
+        }))
+    }
+}
+
 // TODO: Make a version of this to consume filtered tokens? any_filtered
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -73,7 +133,7 @@ pub fn one<'text, Sc>(token: Sc::Token)
         -> ParseResult<'text, Sc, Sc::Token>
     where Sc: Scanner,
 {
-    move |mut lexer, _ctx| {
+    move |mut lexer, ctx| {
         let _trace_span = span!(Level::TRACE, "one", token=?token).entered();
         let error_span = lexer.parse_span();
 
@@ -81,6 +141,7 @@ pub fn one<'text, Sc>(token: Sc::Token)
             // Matching token.
             Some(lex) if lex == token => {
                 event!(Level::TRACE, "success ({:?})", lex);
+                ctx.report_progress(lexer.cursor_pos());
                 Ok(Success {
                     lexer,
                     value: lex,
@@ -113,6 +174,231 @@ pub fn one<'text, Sc>(token: Sc::Token)
     }
 }
 
+/// Returns a parser which consumes a single token if it matches the given
+/// token, returning the source text of the token.
+///
+/// This is equivalent to `text(one(...))`.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn one_text<'text, Sc>(token: Sc::Token)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, &'text str>
+    where Sc: Scanner,
+{
+    let mut parser = one(token);
+
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "one_text").entered();
+        let succ = (parser)(lexer, ctx)?;
+        let value = token_text(&succ.lexer);
+        Ok(Success {
+            lexer: succ.lexer,
+            value,
+        })
+    }
+}
+
+/// Returns a parser which consumes a single token if it has the same enum
+/// variant as the given token, ignoring any data carried by the variant.
+///
+/// The matched token is returned, so this can be used to match any token of a
+/// payload-carrying variant (such as `Ident(String)`) and retrieve its value.
+/// Errors describe the expected token by its variant name only, so the data of
+/// the given token is never displayed.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn one_kind<'text, Sc>(token: Sc::Token)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Sc::Token>
+    where Sc: Scanner,
+{
+    let kind = std::mem::discriminant(&token);
+    let kind_name = token_kind_name(&token);
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "one_kind", token=?token)
+            .entered();
+        let error_span = lexer.parse_span();
+
+        match lexer.next() {
+            // Matching token kind.
+            Some(lex) if std::mem::discriminant(&lex) == kind => {
+                event!(Level::TRACE, "success ({:?})", lex);
+                ctx.report_progress(lexer.cursor_pos());
+                Ok(Success {
+                    lexer,
+                    value: lex,
+                })
+            },
+
+            // Incorrect token.
+            #[cfg_attr(not(feature="tracing"), allow(unused_variables))]
+            Some(lex) => {
+                event!(Level::TRACE, "UnexpectedTokenError ({:?})", lex);
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::Other(kind_name.clone()),
+                    found: Found::Token(lex),
+                }))
+            },
+
+            // Unexpected end-of-text.
+            None => {
+                event!(Level::DEBUG, "UnexpectedTokenError (EndOfText)");
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::<Sc::Token>::Other(kind_name.clone()),
+                    found: Found::EndOfText,
+                }))
+            },
+        }
+    }
+}
+
+/// Returns the name of the given token's enum variant, omitting any data
+/// carried by the variant.
+fn token_kind_name<T>(token: &T) -> String where T: std::fmt::Debug {
+    let name = format!("{token:?}");
+    let end = name
+        .find(|c: char| c == '(' || c == '{' || c.is_whitespace())
+        .unwrap_or(name.len());
+    name[..end].to_owned()
+}
+
+/// Returns a parser which consumes a single token if it matches the given
+/// token, returning its span.
+///
+/// ### Error recovery
+///
+/// If the token is not found, a `MissingTokenError` with the given message is
+/// sent to the error sink and a synthetic token is inserted in its place. The
+/// returned span is then the empty span at the current position, and no input
+/// is consumed.
+pub fn expect_or_insert<'text, Sc, M>(token: Sc::Token, message: M)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Span>
+    where
+        Sc: Scanner,
+        M: Into<String>,
+{
+    let message = message.into();
+    let mut one_parser = one(token.clone());
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "expect_or_insert",
+                token=?token)
+            .entered();
+
+        match (one_parser)(lexer.clone(), ctx.clone()) {
+            Ok(succ) => {
+                let token_span = succ.lexer.token_span();
+                Ok(succ.map_value(|_| token_span))
+            },
+
+            Err(_) => {
+                event!(Level::DEBUG, "inserting synthetic token");
+                let error_span = Span::at(lexer.cursor_pos());
+                let parse_error = Box::new(MissingTokenError {
+                    error_span,
+                    expected: token.clone(),
+                    message: message.clone(),
+                });
+                match ctx.send_error(parse_error) {
+                    Err(parse_error) => Err(parse_error),
+                    Ok(()) => Ok(Success {
+                        lexer,
+                        value: error_span,
+                    }),
+                }
+            },
+        }
+    }
+}
+
+/// Returns a parser which consumes a single token if it has the same enum
+/// variant as the given identifier token and its source text equals the given
+/// word.
+///
+/// This allows a word to be treated as a keyword in some contexts while being
+/// scanned as an ordinary identifier, so the scanner needs no knowledge of
+/// contextual keywords. The text is compared against the raw source text of
+/// the token.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+// The token is taken by value for consistency with the other token parsers.
+#[allow(clippy::needless_pass_by_value)]
+pub fn contextual_keyword<'text, Sc, W>(ident_token: Sc::Token, word: W)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Sc::Token>
+    where
+        Sc: Scanner,
+        W: Into<String>,
+{
+    let word = word.into();
+    let kind = std::mem::discriminant(&ident_token);
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "contextual_keyword",
+                word=?word)
+            .entered();
+        let error_span = lexer.parse_span();
+
+        match lexer.next() {
+            // Matching token kind and text.
+            Some(lex) if std::mem::discriminant(&lex) == kind
+                && token_text(&lexer) == word =>
+            {
+                event!(Level::TRACE, "success ({:?})", lex);
+                ctx.report_progress(lexer.cursor_pos());
+                Ok(Success {
+                    lexer,
+                    value: lex,
+                })
+            },
+
+            // Incorrect token or text.
+            #[cfg_attr(not(feature="tracing"), allow(unused_variables))]
+            Some(lex) => {
+                event!(Level::TRACE, "UnexpectedTokenError ({:?})", lex);
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::Other(format!("'{word}'")),
+                    found: Found::Token(lex),
+                }))
+            },
+
+            // Unexpected end-of-text.
+            None => {
+                event!(Level::DEBUG, "UnexpectedTokenError (EndOfText)");
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::<Sc::Token>::Other(
+                        format!("'{word}'")),
+                    found: Found::EndOfText,
+                }))
+            },
+        }
+    }
+}
+
+/// Returns the source text of the most recently consumed token.
+fn token_text<'text, Sc>(lexer: &Lexer<'text, Sc>) -> &'text str
+    where Sc: Scanner,
+{
+    let source_text = lexer.source_text();
+    let offset = source_text.start_position().byte;
+    let span = lexer.token_span();
+    let text: &'text str = source_text.text();
+    &text[span.start().byte - offset..span.end().byte - offset]
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // any
 ////////////////////////////////////////////////////////////////////////////////
@@ -129,7 +415,7 @@ pub fn any<'text, 'a, Sc>(tokens: &'a [Sc::Token])
 {
     assert!(!tokens.is_empty(), "empty token slice not supported");
 
-    move |mut lexer, _ctx| {
+    move |mut lexer, ctx| {
         let _trace_span = span!(Level::TRACE, "any").entered();
         let error_span = lexer.parse_span();
 
@@ -141,6 +427,7 @@ pub fn any<'text, 'a, Sc>(tokens: &'a [Sc::Token])
                     if lex == *token {
                         let _ = lexer.next();
                         event!(Level::TRACE, "success ({:?})", lex);
+                        ctx.report_progress(lexer.cursor_pos());
                         return Ok(Success {
                             value: token.clone(),
                             lexer,
@@ -171,6 +458,32 @@ pub fn any<'text, 'a, Sc>(tokens: &'a [Sc::Token])
     }
 }
 
+/// Returns a parser which attempts to match each of the given tokens in
+/// sequence, returning the source text of the first which succeeds.
+///
+/// This is equivalent to `text(any(...))`.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn any_text<'text, 'a, Sc>(tokens: &'a [Sc::Token])
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, &'text str> + 'a
+    where Sc: Scanner + 'a,
+{
+    let mut parser = any(tokens);
+
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "any_text").entered();
+        let succ = (parser)(lexer, ctx)?;
+        let value = token_text(&succ.lexer);
+        Ok(Success {
+            lexer: succ.lexer,
+            value,
+        })
+    }
+}
+
 /// Returns a parser which attempts to match each of the given tokens in
 /// sequence, returning the index of the first which succeeds.
 ///
@@ -184,7 +497,7 @@ pub fn any_index<'text, 'a, Sc>(tokens: &'a [Sc::Token])
 {
     assert!(!tokens.is_empty(), "empty token slice not supported");
 
-    move |mut lexer, _ctx| {
+    move |mut lexer, ctx| {
         let _trace_span = span!(Level::TRACE, "any_index").entered();
         let error_span = lexer.parse_span();
 
@@ -196,6 +509,7 @@ pub fn any_index<'text, 'a, Sc>(tokens: &'a [Sc::Token])
                     if lex == *token {
                         let _ = lexer.next();
                         event!(Level::TRACE, "success ({:?}@{:?})", lex, idx);
+                        ctx.report_progress(lexer.cursor_pos());
                         return Ok(Success {
                             value: idx,
                             lexer,
@@ -226,7 +540,59 @@ pub fn any_index<'text, 'a, Sc>(tokens: &'a [Sc::Token])
     }
 }
 
-/// Struct for displaying tokens in `any` errors.
+/// Returns a parser which matches any token not among the given tokens.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn none_of<'text, 'a, Sc>(tokens: &'a [Sc::Token])
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Sc::Token> + 'a
+    where Sc: Scanner,
+{
+    assert!(!tokens.is_empty(), "empty token slice not supported");
+
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "none_of").entered();
+        let error_span = lexer.parse_span();
+
+        match lexer.next() {
+            Some(lex) if !tokens.contains(&lex) => {
+                event!(Level::TRACE, "success ({:?})", lex);
+                ctx.report_progress(lexer.cursor_pos());
+                Ok(Success {
+                    value: lex,
+                    lexer,
+                })
+            },
+
+            Some(lex) => {
+                event!(Level::TRACE, "UnexpectedTokenError ({:?})", lex);
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::Other(
+                        format!("none of {}", DisplayList(tokens))),
+                    found: Found::Token(lex),
+                }))
+            },
+
+            // Unexpected end-of-text.
+            None => {
+                event!(Level::DEBUG, "UnexpectedTokenError (EndOfText)");
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::<Sc::Token>::Other(
+                        format!("none of {}", DisplayList(tokens))),
+                    found: Found::EndOfText,
+                }))
+            },
+        }
+    }
+}
+
+/// Struct for displaying tokens in `any` and `none_of` errors.
 struct DisplayList<'a, T>(&'a[T]);
 
 impl<'a, T> std::fmt::Display for DisplayList<'a, T>
@@ -278,7 +644,7 @@ pub fn seq<'text, 'a, Sc>(tokens: &'a [Sc::Token])
     // is potential value in constructing the result incrementally as we do.
 
     let cap = tokens.len();
-    move |mut lexer, _ctx| {
+    move |mut lexer, ctx| {
         let _trace_span = span!(Level::TRACE, "seq").entered();
         let error_span = lexer.parse_span();
         let mut found = Vec::with_capacity(cap);
@@ -290,6 +656,7 @@ pub fn seq<'text, 'a, Sc>(tokens: &'a [Sc::Token])
                 // Matching token.
                 Some(lex) if lex == *token => {
                     event!(Level::TRACE, "found ({:?})", lex);
+                    ctx.report_progress(lexer.cursor_pos());
                     found.push(lex)
                 },
 
@@ -324,6 +691,23 @@ pub fn seq<'text, 'a, Sc>(tokens: &'a [Sc::Token])
     }
 }
 
+/// Returns a parser attempts each of the given tokens in sequence, returning
+/// the success only if each succeeds. The parsed tokens are returned together
+/// with their enclosing span.
+///
+/// This is equivalent to `spanned(seq(...))`.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn seq_spanned<'text: 'a, 'a, Sc>(tokens: &'a [Sc::Token])
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<Vec<Sc::Token>>> + 'a
+    where Sc: Scanner + 'a,
+{
+    spanned(seq(tokens))
+}
+
 /// Returns a parser attempts each of the given tokens in sequence, returning
 /// the number of tokens successfully parsed.
 /// 
@@ -337,7 +721,7 @@ pub fn seq_count<'text, 'a, Sc>(tokens: &'a [Sc::Token])
         -> ParseResult<'text, Sc, usize> + 'a
     where Sc: Scanner,
 {
-    move |mut lexer, _ctx| {
+    move |mut lexer, ctx| {
         let _trace_span = span!(Level::TRACE, "seq_count").entered();
         let error_span = lexer.parse_span();
         
@@ -353,6 +737,7 @@ pub fn seq_count<'text, 'a, Sc>(tokens: &'a [Sc::Token])
                     event!(Level::TRACE, "found ({:?})", lex);
                     count += 1;
                     let _ = lexer.next();
+                    ctx.report_progress(lexer.cursor_pos());
                 }
 
                 // Incorrect token.
@@ -392,7 +777,7 @@ pub fn pred<'text, Sc>(expr: Expr<Sc::Token>)
     where Sc: Scanner,
 {
     let pred = DnfVec::from(expr.map(Token));
-    move |mut lexer, _ctx| {
+    move |mut lexer, ctx| {
         let error_span = lexer.parse_span();
 
         match lexer.next() {
@@ -409,6 +794,7 @@ pub fn pred<'text, Sc>(expr: Expr<Sc::Token>)
 
             // Matching token.
             Some(lex) if pred.eval(&lex) => {
+                ctx.report_progress(lexer.cursor_pos());
                 Ok(Success {
                     lexer,
                     value: lex,
@@ -429,6 +815,62 @@ pub fn pred<'text, Sc>(expr: Expr<Sc::Token>)
     }
 }
 
+/// Returns a parser which consumes a single token if it satisfies the given
+/// predicate function. The `expected` description is used in the error
+/// message if the token does not satisfy the predicate.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn satisfy<'text, Sc, P>(pred: P, expected: &'static str)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Sc::Token>
+    where
+        Sc: Scanner,
+        P: Fn(&Sc::Token) -> bool,
+{
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::TRACE, "satisfy", expected).entered();
+        let error_span = lexer.parse_span();
+
+        match lexer.next() {
+            // Unexpected end-of-text.
+            None => {
+                event!(Level::DEBUG, "UnexpectedTokenError (EndOfText)");
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::<Sc::Token>::Other(
+                        expected.to_owned()),
+                    found: Found::EndOfText,
+                }))
+            },
+
+            // Matching token.
+            Some(lex) if (pred)(&lex) => {
+                event!(Level::TRACE, "success ({:?})", lex);
+                ctx.report_progress(lexer.cursor_pos());
+                Ok(Success {
+                    lexer,
+                    value: lex,
+                })
+            },
+
+            // Incorrect token.
+            #[cfg_attr(not(feature="tracing"), allow(unused_variables))]
+            Some(lex) => {
+                event!(Level::TRACE, "UnexpectedTokenError ({:?})", lex);
+                Err(Box::new(UnexpectedTokenError {
+                    error_span,
+                    token_span: lexer.token_span(),
+                    expected: Expected::Other(expected.to_owned()),
+                    found: Found::Token(lex),
+                }))
+            },
+        }
+    }
+}
+
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Token<T>(T);
@@ -445,7 +887,8 @@ impl<T> Eval for Token<T> where T: Clone + PartialEq {
 ////////////////////////////////////////////////////////////////////////////////
 // end-of-text
 ////////////////////////////////////////////////////////////////////////////////
-/// Parses the end of the text.
+/// Parses the end of the text. If the lexer has a terminator token, this also
+/// succeeds before the terminator.
 ///
 /// ### Error recovery
 ///
@@ -458,7 +901,7 @@ pub fn end_of_text<'text, Sc>(
 {
     let error_span = lexer.parse_span();
 
-    if lexer.is_empty() {
+    if lexer.is_empty() || lexer.is_at_terminator() {
         event!(Level::TRACE, "end_of_text: Success");
         Ok(Success {
             lexer,
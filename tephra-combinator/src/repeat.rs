@@ -12,15 +12,34 @@
 use crate::discard;
 use crate::empty;
 use crate::one;
+use crate::recover;
+use crate::recover_default;
 use crate::right;
+use crate::spanned;
+use crate::stabilize;
+use crate::text;
+use crate::up_to;
 
 // External library imports.
+use tephra::error::NonAdvancingRepetitionError;
+use tephra::error::NotedError;
+use tephra::error::RepeatCountError;
+use tephra::error::UnexpectedSeparatorError;
 use tephra::Context;
 use tephra::Lexer;
 use tephra::ParseResult;
 use tephra::ParseResultExt as _;
 use tephra::Scanner;
+use tephra::Span;
+use tephra::Spanned;
 use tephra::Success;
+use tephra_tracing::event;
+use tephra_tracing::Level;
+use tephra_tracing::span;
+
+// Standard library imports.
+use std::rc::Rc;
+use std::sync::RwLock;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -130,6 +149,132 @@ pub fn repeat_until<'text, Sc, F, G, V, U>(
     }
 }
 
+/// Returns a parser which repeats the given parser between `min` and `max`
+/// times, inclusive. Each parsed value is collected into a `Vec`.
+///
+/// Once `max` values are parsed, the parser is not attempted again, so no text
+/// following the last value is consumed. A committed failure of the parser is
+/// returned rather than ending the repetition.
+///
+/// # Panics
+///
+/// Panics if `max` < `min`.
+///
+/// ### Error recovery
+///
+/// If fewer than `min` values are parsed, a `RepeatCountError` is sent to the
+/// error sink and the parsed values are returned.
+pub fn count<'text, Sc, F, V>(
+    min: usize,
+    max: Option<usize>,
+    mut parser: F)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Vec<V>>
+    where
+        Sc: Scanner,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, V>,
+{
+    if let Some(m) = max {
+        assert!(m >= min, "count with max < min");
+    }
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "count").entered();
+
+        let mut vals = Vec::with_capacity(min);
+        let mut succ = Success { lexer, value: () };
+        while max.map_or(true, |m| vals.len() < m) {
+            match (parser)(succ.lexer.clone(), ctx.clone()) {
+                Ok(next) => {
+                    if !advanced(&succ.lexer, &next.lexer, &ctx) {
+                        break;
+                    }
+                    let (val, next) = next.take_value();
+                    vals.push(val);
+                    succ = next;
+                },
+                Err(fail) if fail.is_committed() => {
+                    event!(Level::TRACE, "count failure committed");
+                    return Err(fail);
+                },
+                Err(_) => break,
+            }
+        }
+
+        if vals.len() < min {
+            event!(Level::DEBUG, "RepeatCountError ({} found)", vals.len());
+            let parse_error = Box::new(RepeatCountError {
+                error_span: succ.lexer.parse_span(),
+                found: vals.len(),
+                expected_min: min,
+                expected_max: max,
+            });
+
+            match ctx.send_error(parse_error) {
+                Err(parse_error) => Err(parse_error),
+                Ok(()) => Ok(succ.map_value(|_| vals)),
+            }
+        } else {
+            Ok(succ.map_value(|_| vals))
+        }
+    }
+}
+
+/// Returns a parser which consumes tokens while the given predicate holds. The
+/// parsed value contains each consumed token and its span, together with the
+/// span enclosing the run. If no tokens are consumed, the run's span is empty
+/// and positioned at the lexer cursor.
+///
+/// Filtered tokens are skipped and are not tested against the predicate.
+///
+/// ### Error recovery
+///
+/// This parser does not fail, so no error recovery is attempted.
+pub fn take_while<'text, Sc, P>(pred: P)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<Vec<Spanned<Sc::Token>>>>
+    where
+        Sc: Scanner,
+        P: Fn(&Sc::Token) -> bool,
+{
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "take_while").entered();
+
+        let mut span = Span::at(lexer.cursor_pos());
+        let mut tokens = Vec::new();
+        while let Some(token) = lexer.next_if(|tok| (pred)(tok)) {
+            ctx.report_progress(lexer.cursor_pos());
+            let token_span = lexer.token_span();
+            span = if tokens.is_empty() {
+                token_span
+            } else {
+                span.enclose(token_span)
+            };
+            tokens.push(Spanned { value: token, span: token_span });
+        }
+        event!(Level::TRACE, "took {} tokens", tokens.len());
+
+        Ok(Success { lexer, value: Spanned { value: tokens, span } })
+    }
+}
+
+/// Returns a parser which consumes tokens until the given predicate holds. The
+/// token satisfying the predicate is not consumed.
+///
+/// This is equivalent to `take_while(|tok| !pred(tok))`.
+///
+/// ### Error recovery
+///
+/// This parser does not fail, so no error recovery is attempted.
+pub fn take_until<'text, Sc, P>(pred: P)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<Vec<Spanned<Sc::Token>>>>
+    where
+        Sc: Scanner,
+        P: Fn(&Sc::Token) -> bool,
+{
+    take_while(move |tok| !(pred)(tok))
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Intersperse combinators.
 ////////////////////////////////////////////////////////////////////////////////
@@ -138,6 +283,9 @@ pub fn repeat_until<'text, Sc, F, G, V, U>(
 /// parse attempts from a secondary parser. The parsed value is the number of
 /// successful parses.
 ///
+/// The parsed values are discarded as they are produced, so no allocation is
+/// made for them.
+///
 /// # Panics
 ///
 /// Panics if `high` < `low`.
@@ -162,9 +310,13 @@ pub fn intersperse_count<'text, Sc, F, G, V, U>(
     }
 }
 
-/// Returns a parser which repeats the given number of times, interspersed by
-/// parse attempts from a secondary parser. The parsed value is the number of
-/// successful parses.
+/// Returns a parser which repeats the given number of times or until a stop
+/// parser succeeds, interspersed by parse attempts from a secondary parser.
+/// The parsed value is the number of successful parses. The stop parse is not
+/// counted.
+///
+/// The parsed values are discarded as they are produced, so no allocation is
+/// made for them.
 ///
 /// # Panics
 ///
@@ -253,6 +405,9 @@ pub fn intersperse<'text, Sc, F, G, V, U>(
                 (succ.lexer.clone(), ctx.clone())
             {
                 Ok(next) => {
+                    if !advanced(&succ.lexer, &next.lexer, &ctx) {
+                        break;
+                    }
                     let (val, next) = next.take_value();
                     vals.push(val);
                     succ = next;
@@ -342,6 +497,9 @@ pub fn intersperse_until<'text, Sc, F, G, H, V, U, T>(
                 (succ.lexer.clone(), ctx.clone())
             {
                 Ok(next) => {
+                    if !advanced(&succ.lexer, &next.lexer, &ctx) {
+                        break;
+                    }
                     let (val, next) = next.take_value();
                     vals.push(val);
                     succ = next;
@@ -359,6 +517,86 @@ pub fn intersperse_until<'text, Sc, F, G, H, V, U, T>(
 }
 
 
+/// Returns a parser which repeats the given parser, interspersed by parse
+/// attempts from a secondary parser, folding each parsed value into an
+/// accumulator. The parsed value is the final accumulator.
+///
+/// Parsing stops before the next item if the end of text or a token satisfying
+/// the terminator predicate is reached, or if the secondary parser and
+/// following item fail to parse. A committed failure of either parser is
+/// returned rather than ending the sequence. No `Vec` is allocated for the
+/// parsed values.
+///
+/// ### Error recovery
+///
+/// No error recovery is attempted.
+pub fn intersperse_fold<'text, Sc, F, G, P, H, V, U, T>(
+    init: T,
+    mut parser: F,
+    mut inter_parser: G,
+    terminator: P,
+    mut fold_fn: H)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, T>
+    where
+        Sc: Scanner,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, V>,
+        G: FnMut(Lexer<'text, Sc>, Context<'text, Sc>) -> ParseResult<'text, Sc, U>,
+        P: Fn(&Sc::Token) -> bool,
+        H: FnMut(T, V) -> T,
+        T: Clone,
+{
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "intersperse_fold").entered();
+
+        let mut acc = init.clone();
+        match lexer.peek() {
+            None => return Ok(Success { lexer, value: acc }),
+            Some(tok) if (terminator)(&tok) => {
+                event!(Level::TRACE, "found terminator ({:?})", tok);
+                return Ok(Success { lexer, value: acc });
+            },
+            _ => (),
+        }
+
+        let (val, mut succ) = (parser)
+            (lexer, ctx.clone())?
+            .take_value();
+        acc = (fold_fn)(acc, val);
+
+        loop {
+            match succ.lexer.peek() {
+                None => break,
+                Some(tok) if (terminator)(&tok) => {
+                    event!(Level::TRACE, "found terminator ({:?})", tok);
+                    break;
+                },
+                _ => (),
+            }
+
+            match right(&mut inter_parser, &mut parser)
+                (succ.lexer.clone(), ctx.clone())
+            {
+                Ok(next) => {
+                    if !advanced(&succ.lexer, &next.lexer, &ctx) {
+                        break;
+                    }
+                    let (val, next) = next.take_value();
+                    acc = (fold_fn)(acc, val);
+                    succ = next;
+                },
+                Err(fail) if fail.is_committed() => {
+                    event!(Level::TRACE, "intersperse_fold failure committed");
+                    return Err(fail);
+                },
+                Err(_) => break,
+            }
+        }
+
+        Ok(succ.map_value(|_| acc))
+    }
+}
+
 
 /// Returns a parser which repeats the given number of times, interspersed by
 /// parse attempts from a secondary parser. Each parsed value is collected into
@@ -421,6 +659,9 @@ pub fn intersperse_default<'text, Sc, F, V>(
                 (succ.lexer.clone(), ctx.clone())
             {
                 Ok(next) => {
+                    if !advanced(&succ.lexer, &next.lexer, &ctx) {
+                        break;
+                    }
                     let (val, next) = next.take_value();
                     vals.push(val);
                     succ = next;
@@ -436,3 +677,228 @@ pub fn intersperse_default<'text, Sc, F, V>(
         Ok(succ.map_value(|_| vals))
     }
 }
+
+
+/// Returns true if the `next` lexer has advanced beyond the `prev` lexer.
+///
+/// A repeated parse which does not advance would repeat forever, so callers
+/// should stop repeating when this returns false. In debug builds, a
+/// `NonAdvancingRepetitionError` is also sent to the error sink, if present,
+/// to help locate the parser responsible. The diagnostic never causes the
+/// parse to fail, so the parse result is the same in all builds.
+fn advanced<'text, Sc>(
+    prev: &Lexer<'text, Sc>,
+    next: &Lexer<'text, Sc>,
+    ctx: &Context<'text, Sc>)
+    -> bool
+    where Sc: Scanner,
+{
+    if next.cursor_pos() != prev.cursor_pos() {
+        return true;
+    }
+
+    event!(Level::DEBUG, "non-advancing repetition");
+    if cfg!(debug_assertions) {
+        let _ = ctx.send_error(Box::new(NonAdvancingRepetitionError {
+            error_span: Span::at(next.cursor_pos()),
+        }));
+    }
+    false
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Separated list combinators.
+////////////////////////////////////////////////////////////////////////////////
+/// Options controlling the separators accepted by `list_with_options`.
+///
+/// The default options allow a trailing separator and forbid a leading one,
+/// matching the behavior of `list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListOptions {
+    /// Whether a separator may follow the last item.
+    pub allow_trailing: bool,
+    /// Whether a separator may precede the first item.
+    pub allow_leading: bool,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        ListOptions {
+            allow_trailing: true,
+            allow_leading: false,
+        }
+    }
+}
+
+/// Returns a parser which parses a list of items separated by `sep_token`,
+/// ending before a token satisfying `abort_pred` or the end of the text.
+/// Leading and trailing separators are accepted or rejected according to the
+/// given `ListOptions`.
+///
+/// ## Error recovery
+///
+/// Item errors are recovered as in `list`. A leading or trailing separator
+/// which is not permitted emits an `UnexpectedSeparatorError` and is skipped.
+pub fn list_with_options<'text: 'a, 'a, Sc, F, X: 'a, A>(
+    mut parser: F,
+    sep_token: Sc::Token,
+    abort_pred: A,
+    options: ListOptions)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Vec<Option<X>>> + 'a
+    where
+        Sc: Scanner + 'a,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, X> + 'a,
+        A: Fn(&Sc::Token) -> bool + 'static + Clone,
+{
+    let move_token = sep_token.clone();
+    let move_pred = abort_pred.clone();
+    let sep_or_abort_pred = move |tok: &Sc::Token| {
+        tok == &move_token || (move_pred)(tok)
+    };
+
+    let rec_token = sep_token.clone();
+    let rec_pred = abort_pred.clone();
+    let recover_pat = Rc::new(RwLock::new(move |tok: Option<Sc::Token>| {
+        Ok(tok.is_some_and(|tok| tok == rec_token || rec_pred(&tok)))
+    }));
+
+    move |mut lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "list_with_options").entered();
+
+        let mut vals = Vec::new();
+
+        // Parse leading separator.
+        if lexer.peek().as_ref() == Some(&sep_token) {
+            let succ = one(sep_token.clone())(lexer, ctx.clone())?;
+            if !options.allow_leading {
+                event!(Level::DEBUG, "unexpected leading separator");
+                let parse_error = Box::new(UnexpectedSeparatorError {
+                    error_span: succ.lexer.token_span(),
+                    trailing: false,
+                });
+                ctx.send_error(parse_error)?;
+            }
+            lexer = succ.lexer.into_sublexer();
+        }
+
+        loop {
+            // Stop if there is no text remaining or we hit an abort token.
+            match lexer.peek() {
+                None => { break; }
+                Some(tok) if (abort_pred)(&tok) => { break; }
+                _ => (),
+            }
+
+            // Try to parse a value.
+            let (val, succ) = stabilize(recover(
+                    up_to(&mut parser, &sep_or_abort_pred),
+                    recover_pat.clone()))
+                (lexer.clone(), ctx.clone())?
+                .take_value();
+
+            lexer = succ.lexer;
+            vals.push(val);
+            event!(Level::DEBUG, "value captured");
+
+            match lexer.peek() {
+                None => { break; }
+                Some(tok) if (abort_pred)(&tok) => { break; }
+                _ => (),
+            }
+
+            // Try to parse sep token.
+            let at_sep = lexer.peek().as_ref() == Some(&sep_token);
+            let (_, succ) = recover_default(
+                    discard(one(sep_token.clone())),
+                    recover_pat.clone())
+                (lexer.clone(), ctx.clone())?
+                .take_value();
+            let sep_span = succ.lexer.token_span();
+            lexer = succ.lexer.into_sublexer();
+
+            // Check for trailing separator.
+            let at_end = match lexer.peek() {
+                None      => true,
+                Some(tok) => (abort_pred)(&tok),
+            };
+            if at_sep && at_end {
+                if !options.allow_trailing {
+                    event!(Level::DEBUG, "unexpected trailing separator");
+                    let parse_error = Box::new(UnexpectedSeparatorError {
+                        error_span: sep_span,
+                        trailing: true,
+                    });
+                    ctx.send_error(parse_error)?;
+                }
+                break;
+            }
+        }
+
+        Ok(Success { lexer, value: vals })
+    }
+}
+
+/// Returns a parser which parses a path of `ident_token`s separated by
+/// `dot_token`s, such as `group.sub.name`. The parsed value contains the text
+/// and span of each path segment, together with the span enclosing the path.
+///
+/// A `dot_token` which is not followed by an `ident_token` fails with the
+/// failure of the segment parse. A committed failure of the `dot_token` parse
+/// is returned rather than ending the path.
+///
+/// ## Error recovery
+///
+/// No error recovery is attempted.
+pub fn dotted_path<'text, Sc>(ident_token: Sc::Token, dot_token: Sc::Token)
+    -> impl FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+        -> ParseResult<'text, Sc, Spanned<Vec<Spanned<&'text str>>>>
+    where Sc: Scanner,
+{
+    move |lexer, ctx| {
+        let _trace_span = span!(Level::DEBUG, "dotted_path").entered();
+
+        let (first, mut succ) = spanned(text(one(ident_token.clone())))
+            (lexer, ctx.clone())?
+            .take_value();
+        let mut span = first.span;
+        let mut segments = vec![first];
+
+        loop {
+            let dot_succ = match one(dot_token.clone())
+                (succ.lexer.clone(), ctx.clone())
+            {
+                Ok(dot_succ) => dot_succ,
+                Err(fail) if fail.is_committed() => {
+                    event!(Level::TRACE, "path separator failure committed");
+                    return Err(fail);
+                },
+                Err(_) => break,
+            };
+
+            match spanned(text(one(ident_token.clone())))
+                (dot_succ.lexer, ctx.clone())
+            {
+                Ok(next) => {
+                    let (segment, next) = next.take_value();
+                    span = span.enclose(segment.span);
+                    segments.push(segment);
+                    succ = next;
+                },
+                Err(fail) if fail.is_committed() => {
+                    event!(Level::TRACE, "path segment failure committed");
+                    return Err(fail);
+                },
+                Err(fail) => {
+                    event!(Level::DEBUG, "trailing path separator");
+                    return Err(Box::new(NotedError::new(fail,
+                        "expected a path segment after the separator".into())));
+                },
+            }
+        }
+
+        Ok(succ.map_value(|_| Spanned { value: segments, span }))
+    }
+}
@@ -30,6 +30,7 @@ use tephra::ParseError;
 use tephra::ParseResult;
 use tephra::ParseResultExt as _;
 use tephra::Pos;
+use tephra::scan_char_table;
 use tephra::Scanner;
 use tephra::SourceTextRef;
 use tephra::Span;
@@ -104,29 +105,14 @@ impl Scanner for Abc {
         let text = &source.as_ref()[base.byte..];
         let metrics = source.column_metrics();
 
-        if text.starts_with(',') {
-            self.0 = Some(AbcToken::Comma);
-            Some((
-                AbcToken::Comma,
-                metrics.end_position(&source.as_ref()[..=base.byte], base)))
-
-        } else if text.starts_with(';') {
-            self.0 = Some(AbcToken::Semicolon);
-            Some((
-                AbcToken::Semicolon,
-                metrics.end_position(&source.as_ref()[..=base.byte], base)))
-
-        } else if text.starts_with(']') {
-            self.0 = Some(AbcToken::CloseBracket);
-            Some((
-                AbcToken::CloseBracket,
-                metrics.end_position(&source.as_ref()[..=base.byte], base)))
-
-        } else if text.starts_with('[') {
-            self.0 = Some(AbcToken::OpenBracket);
-            Some((
-                AbcToken::OpenBracket,
-                metrics.end_position(&source.as_ref()[..=base.byte], base)))
+        if let Some((token, adv)) = scan_char_table(source, base, &[
+            (',', AbcToken::Comma),
+            (';', AbcToken::Semicolon),
+            (']', AbcToken::CloseBracket),
+            ('[', AbcToken::OpenBracket),
+        ]) {
+            self.0 = Some(token);
+            Some((token, adv))
 
         } else if text.starts_with('a') {
             self.0 = Some(AbcToken::A);
@@ -160,10 +146,9 @@ impl Scanner for Abc {
             let substr = &source.as_ref()[0.. base.byte + substr_len];
             Some((AbcToken::Ws, metrics.end_position(substr, base)))
         } else if !text.is_empty() {
+            // Invalid tokens may be multi-byte, wide, or zero-width chars.
             self.0 = Some(AbcToken::Invalid);
-            Some((
-                AbcToken::Invalid,
-                metrics.end_position(&source.as_ref()[..=base.byte], base)))
+            Some((AbcToken::Invalid, source.next_position(base)?))
         } else {
             self.0 = None;
             None
@@ -12,8 +12,12 @@
 use crate::both;
 use crate::bracket;
 use crate::bracket_index;
+use crate::maybe_parenthesized;
 use crate::one;
 use crate::raw;
+use crate::recover;
+use crate::right;
+use crate::separated_nonempty_list;
 use crate::spanned;
 use crate::test::abc::Abc;
 use crate::test::abc::AbcToken;
@@ -25,9 +29,11 @@ use crate::unrecoverable;
 use ntest::timeout;
 use pretty_assertions::assert_eq;
 use tephra::Context;
+use tephra::error::MatchBracketError;
 use tephra::error::SourceError;
 use tephra::Lexer;
 use tephra::Pos;
+use tephra::recover_before;
 use tephra::SourceText;
 use tephra::Span;
 use tephra::Spanned;
@@ -211,6 +217,138 @@ error: mismatched brackets
 ");
 }
 
+/// Test failed `bracket` combinator bracket error spans. Ensure the span of
+/// each offending bracket is available from the failure.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_bracket::bracket_error_spans -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn bracket_error_spans() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "bracket_error_spans")
+        .entered();
+    use AbcToken::*;
+
+    let bracket_error = |text, open_tokens, close_tokens| {
+        let (lexer, ctx, _errors, _source) = build_test_lexer(text);
+        let failure = bracket_index(
+                open_tokens,
+                pattern,
+                close_tokens, |_| false)
+            (lexer, ctx)
+            .unwrap_err();
+        *failure
+            .into_error()
+            .downcast::<MatchBracketError>()
+            .expect("bracket error")
+    };
+
+    assert_eq!(
+        bracket_error(" abc ", &[OpenBracket], &[CloseBracket]),
+        MatchBracketError::NoneFound {
+            expected_start: Span::at(Pos::new(1, 0, 1)),
+        });
+
+    assert_eq!(
+        bracket_error("[ab   bbb  ", &[OpenBracket], &[CloseBracket]),
+        MatchBracketError::Unclosed {
+            found_start: Span::enclosing(
+                Pos::new(0, 0, 0),
+                Pos::new(1, 0, 1)),
+        });
+
+    assert_eq!(
+        bracket_error(" abc]", &[OpenBracket], &[CloseBracket]),
+        MatchBracketError::Unopened {
+            found_end: Span::enclosing(
+                Pos::new(4, 0, 4),
+                Pos::new(5, 0, 5)),
+        });
+
+    assert_eq!(
+        bracket_error("[abc,",
+            &[OpenBracket, OpenBracket],
+            &[CloseBracket, Comma]),
+        MatchBracketError::Mismatch {
+            found_start: Span::enclosing(
+                Pos::new(0, 0, 0),
+                Pos::new(1, 0, 1)),
+            found_end: Span::enclosing(
+                Pos::new(4, 0, 4),
+                Pos::new(5, 0, 5)),
+        });
+}
+
+/// Test failed `bracket` combinator bracket error spans with error recovery.
+/// Ensure the span of each offending bracket is available from the collected
+/// errors.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_bracket::recover_bracket_error_spans -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn recover_bracket_error_spans() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "recover_bracket_error_spans")
+        .entered();
+    use AbcToken::*;
+
+    let bracket_error = |text, open_tokens, close_tokens| {
+        let (lexer, ctx, errors, _source) = build_test_lexer(text);
+        let (value, _succ) = recover(
+                bracket_index(
+                    open_tokens,
+                    pattern,
+                    close_tokens, |_| false),
+                recover_before(Semicolon))
+            (lexer, ctx)
+            .expect("successful parse")
+            .take_value();
+        assert_eq!(value, None);
+
+        let errors = errors.read().unwrap();
+        assert_eq!(errors.len(), 1);
+        *MatchBracketError::find_in(&errors[0])
+            .expect("bracket error")
+    };
+
+    assert_eq!(
+        bracket_error(" abc ;", &[OpenBracket], &[CloseBracket]),
+        MatchBracketError::NoneFound {
+            expected_start: Span::at(Pos::new(1, 0, 1)),
+        });
+
+    assert_eq!(
+        bracket_error("[ab   bbb  ;", &[OpenBracket], &[CloseBracket]),
+        MatchBracketError::Unclosed {
+            found_start: Span::enclosing(
+                Pos::new(0, 0, 0),
+                Pos::new(1, 0, 1)),
+        });
+
+    assert_eq!(
+        bracket_error(" abc];", &[OpenBracket], &[CloseBracket]),
+        MatchBracketError::Unopened {
+            found_end: Span::enclosing(
+                Pos::new(4, 0, 4),
+                Pos::new(5, 0, 5)),
+        });
+
+    assert_eq!(
+        bracket_error("[abc,;",
+            &[OpenBracket, OpenBracket],
+            &[CloseBracket, Comma]),
+        MatchBracketError::Mismatch {
+            found_start: Span::enclosing(
+                Pos::new(0, 0, 0),
+                Pos::new(1, 0, 1)),
+            found_end: Span::enclosing(
+                Pos::new(4, 0, 4),
+                Pos::new(5, 0, 5)),
+        });
+}
+
 /// Test failed `bracket` combinator with error recovery, with an
 /// unmatched bracket and raw error.
 //
@@ -429,3 +567,125 @@ error: mismatched brackets
   |     ^ ... does not match the closing bracket here
 ");
 }
+
+/// Test successful `maybe_parenthesized` combinator with and without brackets.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_bracket::maybe_parenthesized_nested -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn maybe_parenthesized_nested() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "maybe_parenthesized_nested")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("[[abc]] abc");
+    use AbcToken::*;
+
+    let (value, succ) = both(
+            maybe_parenthesized(OpenBracket, pattern, CloseBracket),
+            maybe_parenthesized(OpenBracket, pattern, CloseBracket))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (
+        Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(2, 0, 2), Pos::new(5, 0, 5)),
+        }),
+        Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(8, 0, 8), Pos::new(11, 0, 11)),
+        }),
+    );
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(11, 0, 11));
+}
+
+/// Test successful `maybe_parenthesized` combinator with content that begins
+/// with a parenthesized item and continues after it.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_bracket::maybe_parenthesized_content_continues -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn maybe_parenthesized_content_continues() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "maybe_parenthesized_content_continues")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("[abc], [abc]");
+    use AbcToken::*;
+
+    let (value, succ) = maybe_parenthesized(
+            OpenBracket,
+            separated_nonempty_list(
+                maybe_parenthesized(OpenBracket, pattern, CloseBracket),
+                Comma,
+                |_| false),
+            CloseBracket)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = vec![
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(4, 0, 4)),
+        })),
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(8, 0, 8), Pos::new(11, 0, 11)),
+        })),
+    ];
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(12, 0, 12));
+}
+
+/// Test successful `maybe_parenthesized` combinator with content that begins
+/// with an open bracket.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_bracket::maybe_parenthesized_content_open_bracket -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn maybe_parenthesized_content_open_bracket() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "maybe_parenthesized_content_open_bracket")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("[[abc] [abc");
+    use AbcToken::*;
+
+    let (value, succ) = both(
+            maybe_parenthesized(
+                OpenBracket,
+                right(one(OpenBracket), pattern),
+                CloseBracket),
+            maybe_parenthesized(
+                OpenBracket,
+                right(one(OpenBracket), pattern),
+                CloseBracket))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (
+        Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(2, 0, 2), Pos::new(5, 0, 5)),
+        }),
+        Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(8, 0, 8), Pos::new(11, 0, 11)),
+        }),
+    );
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(11, 0, 11));
+}
@@ -12,11 +12,14 @@
 use crate::bracket;
 use crate::list;
 use crate::implies;
+use crate::seq;
 use crate::sub;
 use crate::test::abc::Abc;
 use crate::test::abc::AbcToken;
 use crate::test::abc::pattern;
 use crate::test::abc::Pattern;
+use crate::text;
+use crate::unambiguous;
 
 // External library imports.
 use ntest::timeout;
@@ -24,6 +27,7 @@ use pretty_assertions::assert_eq;
 use tephra::Context;
 use tephra::error::SourceError;
 use tephra::Lexer;
+use tephra::ParseResult;
 use tephra::Pos;
 use tephra::SourceText;
 use tephra::Span;
@@ -213,3 +217,72 @@ fn pattern_implies_list() {
     assert_eq!(actual, expected);
     assert_eq!(succ.lexer.cursor_pos(), Pos::new(13, 0, 13));
 }
+
+
+/// Test successful `unambiguous` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_cond::pattern_unambiguous -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_unambiguous() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_unambiguous")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc");
+    use AbcToken::*;
+
+    let mut abc = text(pattern);
+    let mut bc = text(seq(&[B, C]));
+    let mut parsers: [&mut dyn FnMut(
+            Lexer<'static, Abc>,
+            Context<'static, Abc>)
+        -> ParseResult<'static, Abc, &'static str>; 2] = [&mut abc, &mut bc];
+
+    let (value, succ) = unambiguous(&mut parsers[..])
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = "abc";
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+}
+
+/// Test failed `unambiguous` combinator with multiple matches.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_cond::pattern_unambiguous_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_unambiguous_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_unambiguous_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("abc");
+    use AbcToken::*;
+
+    let mut abc = text(pattern);
+    let mut ab = text(seq(&[A, B]));
+    let mut parsers: [&mut dyn FnMut(
+            Lexer<'static, Abc>,
+            Context<'static, Abc>)
+        -> ParseResult<'static, Abc, &'static str>; 2] = [&mut abc, &mut ab];
+
+    let actual = unambiguous(&mut parsers[..])
+        (lexer, ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: ambiguous parse
+ --> (0:0-0:3, bytes 0-3)
+  | 
+0 | abc
+  | ^^^ input matches multiple interpretations
+  = note: interpretation 0 matched (0:0-0:3, bytes 0-3)
+  = note: interpretation 1 matched (0:0-0:2, bytes 0-2)
+");
+}
@@ -9,28 +9,70 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Internal library imports.
+use crate::any;
+use crate::any_text;
 use crate::both;
+use crate::both_sep;
+use crate::both_spanned;
 use crate::center;
+use crate::center_spanned;
+use crate::choice;
+use crate::commit_after;
+use crate::contextual_keyword;
+use crate::delimited;
+use crate::either;
+use crate::either_of;
+use crate::empty;
+use crate::Either;
+use crate::expect_or_insert;
+use crate::fail;
+use crate::fail_at;
+use crate::fold_left;
+use crate::fold_right;
 use crate::left;
+use crate::left_spanned;
+use crate::map;
+use crate::maybe;
+use crate::none_of;
 use crate::one;
+use crate::one_text;
 use crate::raw;
 use crate::sub;
+use crate::text;
 use crate::recover;
 use crate::right;
+use crate::right_spanned;
+use crate::section;
+use crate::separated_pair;
+use crate::seq;
 use crate::stabilize;
+use crate::unambiguous;
+use crate::unrecoverable;
+use crate::spanned;
+use crate::test::abc::abc;
 use crate::test::abc::Abc;
 use crate::test::abc::AbcToken;
+use crate::test::abc::bxx;
 use crate::test::abc::pattern;
 use crate::test::abc::Pattern;
+use crate::test::abc::xyc;
 
 // External library imports.
 use ntest::timeout;
 use pretty_assertions::assert_eq;
+use tephra::CommitPolicy;
 use tephra::Context;
+use tephra::error::FailureError;
+use tephra::error::NotedError;
 use tephra::error::SourceError;
 use tephra::Lexer;
+use tephra::parse_collecting;
+use tephra::parse_continuing;
+use tephra::ParseResult;
+use tephra::ParseResultExt as _;
 use tephra::Pos;
 use tephra::recover_before;
+use tephra::recover_to_balanced;
 use tephra::SourceText;
 use tephra::Span;
 use tephra::Spanned;
@@ -148,202 +190,1992 @@ error: unexpected token
 }
 
 
+/// Test successful `satisfy` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_satisfy -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_satisfy() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_satisfy")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("bac");
+    use AbcToken::*;
+
+    use crate::satisfy;
+
+    let (value, succ) = satisfy(|tok| matches!(tok, A | B), "'a' or 'b'")
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = B;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(1, 0, 1));
+}
+
+
+/// Test failed `satisfy` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_satisfy_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_satisfy_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_satisfy_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("dabc dac");
+    use AbcToken::*;
+
+    use crate::satisfy;
+
+    let actual = satisfy(|tok| matches!(tok, A | B), "'a' or 'b'")
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:8, bytes 0-8)
+  | 
+0 | dabc dac
+  | ^ expected 'a' or 'b'; found 'd'
+");
+}
+
+
+/// Test JSON output of a failed `satisfy` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_satisfy_failed_json -- --exact --nocapture > .trace
+#[cfg(feature = "serde")]
+#[test]
+#[timeout(100)]
+fn simple_satisfy_failed_json() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_satisfy_failed_json")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("dabc dac");
+    use AbcToken::*;
+
+    use crate::satisfy;
+
+    let actual = satisfy(|tok| matches!(tok, A | B), "'a' or 'b'")
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err()
+        .to_json();
+
+    let expected = serde_json::json!({
+        "message": "unexpected token",
+        "message_type": "error",
+        "code": null,
+        "source_name": null,
+        "highlights": [{
+            "start_byte": 0,
+            "end_byte": 1,
+            "start_line": 0,
+            "start_col": 0,
+            "end_line": 0,
+            "end_col": 1,
+            "message": "expected 'a' or 'b'; found 'd'",
+            "severity": "error",
+        }],
+    });
+
+    assert_eq!(actual, expected);
+}
+
+
+/// Test LSP diagnostic output of a failed `one` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_one_failed_lsp -- --exact --nocapture > .trace
+#[cfg(feature = "lsp")]
+#[test]
+#[timeout(100)]
+fn simple_one_failed_lsp() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_one_failed_lsp")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("dabc dac");
+    use AbcToken::*;
+
+    use lsp_types::DiagnosticSeverity;
+    use lsp_types::Position;
+    use lsp_types::Range;
+    use lsp_types::Url;
+    use tephra::MessageType;
+
+    let uri = Url::parse("file:///test.abc").unwrap();
+    let actual = one(A)(lexer, ctx)
+        .unwrap_err()
+        .into_lsp_diagnostic(source, &uri);
+
+    assert_eq!(actual.range, Range {
+        start: Position { line: 0, character: 0 },
+        end: Position { line: 0, character: 1 },
+    });
+    assert_eq!(actual.severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(actual.message, "unexpected token");
+    assert_eq!(actual.related_information, None);
+
+    // Columns are measured in UTF-16 code units.
+    let source = SourceText::new("\u{1F600} abc");
+    let span = Span::enclosing(Pos::new(5, 0, 2), Pos::new(8, 0, 5));
+    let actual = SourceError::new(source, "unknown word")
+        .with_primary_span(span, "not a valid word")
+        .with_message_type(MessageType::Warning)
+        .to_lsp_diagnostic(&uri);
+
+    assert_eq!(actual.range, Range {
+        start: Position { line: 0, character: 3 },
+        end: Position { line: 0, character: 6 },
+    });
+    assert_eq!(actual.severity, Some(DiagnosticSeverity::WARNING));
+}
+
+
+/// Test failed `one` combinator with a synthetic source. Ensure the source name
+/// is displayed in angle brackets.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_one_failed_synthetic -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_one_failed_synthetic() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_one_failed_synthetic")
+        .entered();
+    let source = SourceText::synthetic("repl", "b");
+    let lexer = Lexer::new(Abc::new(), source);
+    let ctx = Context::empty();
+    use AbcToken::*;
+
+    let actual = one(A)
+        (lexer, ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> <repl>:(0:0-0:1, bytes 0-1)
+  | 
+0 | b
+  | ^ expected 'a'; found 'b'
+");
+}
+
+
+/// Test failed `one` combinator with a note attached by `map_err_context`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_one_failed_map_err_context -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_one_failed_map_err_context() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_one_failed_map_err_context")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("b");
+    use AbcToken::*;
+
+    let actual = one(A)
+        (lexer, ctx)
+        .map_err_context(|e| Box::new(NotedError::new(e,
+            "while parsing a command".into())))
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:1, bytes 0-1)
+  | 
+0 | b
+  | ^ expected 'a'; found 'b'
+  = note: while parsing a command
+");
+}
+
+
+/// Test `fail` combinator as the final alternative of a choice.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_fail_alternative -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_fail_alternative() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_fail_alternative")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("a c");
+    use AbcToken::*;
+
+    let (value, succ) = either(one(A), fail("expected a command"))
+        (lexer.clone(), ctx.clone())
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, A);
+
+    let failure = either(one(B), fail::<_, AbcToken, _>("expected a command"))
+        (succ.lexer, ctx)
+        .unwrap_err();
+
+    assert!(failure.is_recoverable());
+    assert_eq!(failure.error_span(), Some(Span::at(Pos::new(1, 0, 1))));
+    assert_eq!(failure.into_source_error(source).message(),
+        "expected a command");
+}
+
+
+/// Test successful `contextual_keyword` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_contextual_keyword -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_contextual_keyword() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_contextual_keyword")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a b");
+    use AbcToken::*;
+
+    let (value, succ) = contextual_keyword(A, "a")
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, A);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(1, 0, 1));
+}
+
+
+/// Test failed `contextual_keyword` combinator, where the token matches but
+/// its text does not.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_contextual_keyword_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_contextual_keyword_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_contextual_keyword_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("b");
+    use AbcToken::*;
+
+    let actual = contextual_keyword(B, "bb")
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:1, bytes 0-1)
+  | 
+0 | b
+  | ^ expected 'bb'; found 'b'
+");
+}
+
+
+/// Test successful `none_of` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_none_of -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_none_of() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_none_of")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc");
+    use AbcToken::*;
+
+    let (value, succ) = none_of(&[D])
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, A);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(1, 0, 1));
+}
+
+
+/// Test successful `one_text` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_one_text -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_one_text() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_one_text")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer(" abc");
+    use AbcToken::*;
+
+    let (value, succ) = one_text(A)
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, "a");
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(2, 0, 2));
+}
+
+
+/// Test successful `any_text` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_any_text -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_any_text() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_any_text")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("bac");
+    use AbcToken::*;
+
+    let (value, succ) = any_text(&[A, B])
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, "b");
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(1, 0, 1));
+}
+
+
+/// Test failed `none_of` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_none_of_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_none_of_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_none_of_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("dabc");
+    use AbcToken::*;
+
+    let actual = none_of(&[D])
+        (lexer, ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:4, bytes 0-4)
+  | 
+0 | dabc
+  | ^ expected none of 'd'; found 'd'
+");
+}
+
+
+/// Test successful `either_of` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_either_of -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_either_of() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_either_of")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a b c");
+    use AbcToken::*;
+
+    let (value, succ) = either_of(one(A), both(one(B), one(C)))
+        (lexer.clone(), ctx.clone())
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, Either::Left(A));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(1, 0, 1));
+
+    let (value, succ) = either_of(one(A), both(one(B), one(C)))
+        (succ.lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, Either::Right((B, C)));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
+}
+
+
+/// Test failed `either_of` combinator. Ensure the failure which progressed
+/// furthest is returned.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_either_of_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_either_of_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_either_of_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("b d");
+    use AbcToken::*;
+
+    let actual = either_of(one(A), both(one(B), one(C)))
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:3, bytes 0-3)
+  | 
+0 | b d
+  |   ^ expected 'c'; found 'd'
+");
+}
+
+
+/// Parses an 'ABC' pattern.
+fn abc_pattern<'text>(lexer: Lexer<'text, Abc>, ctx: Context<'text, Abc>)
+    -> ParseResult<'text, Abc, Pattern<'text>>
+{
+    spanned(text(abc))
+        (lexer, ctx)
+        .map_value(Pattern::Abc)
+}
+
+/// Parses a 'BXX' pattern.
+fn bxx_pattern<'text>(lexer: Lexer<'text, Abc>, ctx: Context<'text, Abc>)
+    -> ParseResult<'text, Abc, Pattern<'text>>
+{
+    spanned(text(bxx))
+        (lexer, ctx)
+        .map_value(Pattern::Bxx)
+}
+
+/// Parses an 'XYC' pattern.
+fn xyc_pattern<'text>(lexer: Lexer<'text, Abc>, ctx: Context<'text, Abc>)
+    -> ParseResult<'text, Abc, Pattern<'text>>
+{
+    spanned(text(xyc))
+        (lexer, ctx)
+        .map_value(Pattern::Xyc)
+}
+
+/// Test successful `choice` combinator. Ensure the result matches `pattern`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_choice -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_choice() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_choice").entered();
+
+    for input in ["abc", "bdd", "dac"] {
+        let (lexer, ctx, _errors, _source) = build_test_lexer(input);
+
+        let mut parsers: [&mut dyn FnMut(
+                Lexer<'static, Abc>,
+                Context<'static, Abc>)
+            -> ParseResult<'static, Abc, Pattern<'static>>; 3] = [
+            &mut abc_pattern,
+            &mut bxx_pattern,
+            &mut xyc_pattern,
+        ];
+        let (value, succ) = choice(&mut parsers)
+            (lexer.clone(), ctx.clone())
+            .expect("successful parse")
+            .take_value();
+        let (expected, expected_succ) = pattern
+            (lexer, ctx)
+            .expect("successful parse")
+            .take_value();
+
+        assert_eq!(value, expected);
+        assert_eq!(succ.lexer.cursor_pos(), expected_succ.lexer.cursor_pos());
+    }
+}
+
+/// Test failed `choice` combinator. Ensure the failure of each alternative is
+/// noted.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_choice_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_choice_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_choice_failed").entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("\n    aaa");
+
+    let mut parsers: [&mut dyn FnMut(
+            Lexer<'static, Abc>,
+            Context<'static, Abc>)
+        -> ParseResult<'static, Abc, Pattern<'static>>; 3] = [
+        &mut abc_pattern,
+        &mut bxx_pattern,
+        &mut xyc_pattern,
+    ];
+    let failure = choice(&mut parsers)
+        (lexer.clone(), ctx)
+        .unwrap_err();
+
+    assert!(!failure.is_committed());
+    let actual = failure.into_source_error(source);
+    assert_eq!(actual.message(), "expected one of 3 alternatives");
+    assert_eq!(format!("{actual}").matches("= note: alternative").count(), 3);
+}
+
+
+/// Test failed `fail_at` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_fail_at -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_fail_at() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_fail_at")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("b");
+
+    let actual = fail_at::<_, (), _>(
+            Span::enclosing(Pos::new(0, 0, 0), Pos::new(1, 0, 1)),
+            "expected a command")
+        (lexer, ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: expected a command
+ --> (0:0-0:1, bytes 0-1)
+  | 
+0 | b
+  | ^ expected a command
+");
+}
+
+
 /// Test successful `both` combinator.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_both() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_both")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+
+    let (value, succ) = both(pattern, pattern)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (
+        Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
+        }),
+        Pattern::Xyc(Spanned {
+            value: "dac",
+            span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7)),
+        }),
+    );
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+}
+
+
+/// Test successful `both_spanned` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both_spanned -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_both_spanned() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_both_spanned")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+
+    let (value, succ) = both_spanned(pattern, pattern)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: (
+            Pattern::Abc(Spanned {
+                value: "abc",
+                span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
+            }),
+            Pattern::Xyc(Spanned {
+                value: "dac",
+                span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7)),
+            }),
+        ),
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(7, 0, 7)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+}
+
+
+/// Test successful `both` combinator over alternative patterns. Ensure each
+/// token is scanned only once.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both_scan_count -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_both_scan_count() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_both_scan_count")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+    let lexer = lexer.with_token_cache(true);
+
+    let succ = both(pattern, pattern)
+        (lexer, ctx)
+        .expect("successful parse");
+
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+    // Tokens: 'a', 'b', 'c', whitespace, 'd', 'a', 'c'.
+    assert_eq!(succ.lexer.scan_count(), 7);
+}
+
+
+/// Test successful `left_spanned` and `right_spanned` combinators.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_left_right_spanned -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_left_right_spanned() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_left_right_spanned")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+
+    let (value, succ) = left_spanned(pattern, pattern)
+        (lexer.clone(), ctx.clone())
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
+        }),
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(7, 0, 7)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+
+    let (value, succ) = right_spanned(pattern, pattern)
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: Pattern::Xyc(Spanned {
+            value: "dac",
+            span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7)),
+        }),
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(7, 0, 7)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+}
+
+
+/// Test successful `center_spanned` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_spanned -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_spanned() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_center_spanned")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("[ abc ]");
+    use AbcToken::*;
+
+    let (value, succ) = center_spanned(
+            one(OpenBracket),
+            pattern,
+            one(CloseBracket))
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(2, 0, 2), Pos::new(5, 0, 5)),
+        }),
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(7, 0, 7)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+}
+
+
+/// Test successful `both_sep` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both_sep -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_both_sep() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_both_sep")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc, dac");
+    use AbcToken::*;
+
+    let (value, succ) = both_sep(pattern, Comma, pattern)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (
+        Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
+        }),
+        Pattern::Xyc(Spanned {
+            value: "dac",
+            span: Span::enclosing(Pos::new(5, 0, 5), Pos::new(8, 0, 8)),
+        }),
+    );
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(8, 0, 8));
+}
+
+
+/// Test failed `both_sep` combinator with a missing separator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both_sep_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_both_sep_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_both_sep_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("abc dac");
+    use AbcToken::*;
+
+    let actual = both_sep(pattern, Comma, pattern)
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc dac
+  |     ^ expected ','; found 'd'
+");
+}
+
+
+/// Test successful `left` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_left -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_left() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_left")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+
+    let (value, succ) = left(pattern, pattern)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+
+    let actual = value;
+    let expected = Pattern::Abc(Spanned {
+        value: "abc",
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
+    });
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+}
+
+
+/// Test successful `right` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_right -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_right() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_right")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+
+    let (value, succ) = right(pattern, pattern)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+
+    let actual = value;
+    let expected = Pattern::Xyc(Spanned {
+        value: "dac",
+        span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7)),
+    });
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+}
+
+
+/// Test `right` combinator failure. Ensure error is properly wrapped.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_right_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_right_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_right_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("abc ddd");
+
+    let actual = right(pattern, sub(pattern))
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: expected pattern
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc ddd
+  |     ^^^ expected 'ABC', 'BXX', or 'XYC' pattern
+");
+}
+
+
+/// Test failed `right` combinator with `raw` wrapper. Ensure error is not
+/// wrapped.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_right_failed_raw -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_right_failed_raw() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_right_failed_raw")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("abc ddd");
+
+    let actual = raw(right(pattern, pattern))
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc ddd
+  |       ^ expected 'c'; found 'd'
+");
+}
+
+
+/// Test failed `left` combinator where the first parser is unrecoverable.
+/// Ensure the second parser is not attempted and the error is not wrapped.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_left_failed_unrecoverable -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_left_failed_unrecoverable() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_left_failed_unrecoverable")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("abc dac");
+    use AbcToken::*;
+
+    let attempted = Rc::new(RwLock::new(false));
+    let second_attempted = attempted.clone();
+
+    let actual = left(
+            unrecoverable(one(D)),
+            move |lexer, ctx| {
+                *second_attempted.write().unwrap() = true;
+                pattern(lexer, ctx)
+            })
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert!(!*attempted.read().unwrap());
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc dac
+  | ^ expected 'd'; found 'a'
+");
+}
+
+
+/// Test failed `right` combinator where the first parser is unrecoverable.
+/// Ensure the second parser is not attempted and the error is not wrapped.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_right_failed_unrecoverable -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_right_failed_unrecoverable() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_right_failed_unrecoverable")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("abc dac");
+    use AbcToken::*;
+
+    let attempted = Rc::new(RwLock::new(false));
+    let second_attempted = attempted.clone();
+
+    let actual = right(
+            unrecoverable(one(D)),
+            move |lexer, ctx| {
+                *second_attempted.write().unwrap() = true;
+                pattern(lexer, ctx)
+            })
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert!(!*attempted.read().unwrap());
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc dac
+  | ^ expected 'd'; found 'a'
+");
+}
+
+
+/// Test successful `center` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_center")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("[abc]");
+    use AbcToken::*;
+
+    let (value, succ) = center(
+            one(OpenBracket),
+            pattern,
+            one(CloseBracket))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Pattern::Abc(Spanned {
+        value: "abc",
+        span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(4, 0, 4)),
+    });
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
+}
+
+
+/// Test successful `delimited` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_delimited -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_delimited() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_delimited")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("[b]");
+    use AbcToken::*;
+
+    let (value, succ) = delimited(
+            one(OpenBracket),
+            one(B),
+            one(CloseBracket))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = B;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+}
+
+
+/// Test successful `separated_pair` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_separated_pair -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_separated_pair() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_separated_pair")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a,c");
+    use AbcToken::*;
+
+    let (value, succ) = separated_pair(one(A), one(Comma), one(C))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (A, C);
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+}
+
+
+/// Test failed `center` combinator with error recovery.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_center_recover")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab]");
+    use AbcToken::*;
+
+    let (value, succ) = center(
+            one(OpenBracket),
+            recover(sub(pattern), recover_before(CloseBracket)),
+            stabilize(one(CloseBracket)))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = None;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(4, 0, 4));
+
+    assert_eq!(errors.read().unwrap().len(), 1);
+    assert_eq!(format!("{}", errors.write().unwrap().pop().unwrap()), "\
+error: expected pattern
+ --> (0:0-0:4, bytes 0-4)
+  | 
+0 | [ab]
+  |  ^^ expected 'ABC', 'BXX', or 'XYC' pattern
+");
+}
+
+
+/// Test failed `center` combinator with error recovery over nested brackets.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_balanced -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover_balanced() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_center_recover_balanced")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[a [b c] d]");
+    use AbcToken::*;
+
+    let (value, succ) = center(
+            one(OpenBracket),
+            recover(sub(pattern),
+                recover_to_balanced([OpenBracket], [CloseBracket])),
+            stabilize(one(CloseBracket)))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = None;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(11, 0, 11));
+    assert_eq!(errors.read().unwrap().len(), 1);
+}
+
+
+/// Test `recover_to_balanced` reaching the end of text with unbalanced
+/// brackets.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::recover_to_balanced_unbalanced -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn recover_to_balanced_unbalanced() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "recover_to_balanced_unbalanced")
+        .entered();
+    let (mut lexer, _ctx, _errors, _source) = build_test_lexer("[a [b");
+    use AbcToken::*;
+
+    assert_eq!(lexer.next(), Some(OpenBracket));
+    lexer.set_recover_state(
+        Some(recover_to_balanced([OpenBracket], [CloseBracket])));
+
+    assert!(lexer.advance_to_recover().is_err());
+    assert!(lexer.is_empty());
+    assert_eq!(lexer.cursor_pos(), Pos::new(5, 0, 5));
+}
+
+/// Test `recover_to_balanced` being reused after reaching the end of text with
+/// unbalanced brackets.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::recover_to_balanced_reused -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn recover_to_balanced_reused() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "recover_to_balanced_reused")
+        .entered();
+    use AbcToken::*;
+    let recover = recover_to_balanced([OpenBracket], [CloseBracket]);
+
+    let (mut lexer, _ctx, _errors, _source) = build_test_lexer("[a [b");
+    assert_eq!(lexer.next(), Some(OpenBracket));
+    lexer.set_recover_state(Some(recover.clone()));
+    assert!(lexer.advance_to_recover().is_err());
+
+    let (mut lexer, _ctx, _errors, _source) = build_test_lexer("[a]");
+    assert_eq!(lexer.next(), Some(OpenBracket));
+    lexer.set_recover_state(Some(recover.clone()));
+    assert!(lexer.advance_to_recover().is_ok());
+    assert_eq!(lexer.cursor_pos(), Pos::new(2, 0, 2));
+
+    let (mut lexer, _ctx, _errors, _source) = build_test_lexer("[a [b] c]");
+    assert_eq!(lexer.next(), Some(OpenBracket));
+    lexer.set_recover_state(Some(recover));
+    assert!(lexer.advance_to_recover().is_ok());
+    assert_eq!(lexer.cursor_pos(), Pos::new(8, 0, 8));
+}
+
+
+/// Test failed `center` combinator with error recovery, using a suppressed
+/// `Context`. Ensure the recovered error is not emitted.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_suppressed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover_suppressed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_center_recover_suppressed")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab]");
+    use AbcToken::*;
+
+    let (value, succ) = ctx.suppressed(|ctx| center(
+            one(OpenBracket),
+            recover(sub(pattern), recover_before(CloseBracket)),
+            stabilize(one(CloseBracket)))
+        (lexer.clone(), ctx))
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = None;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(4, 0, 4));
+    assert_eq!(errors.read().unwrap().len(), 0);
+}
+
+
+/// Test failed `center` combinator with error recovery, using a suppressed
+/// `Context` without an error sink. Ensure error recovery remains disabled.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_suppressed_no_sink -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover_suppressed_no_sink() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_center_recover_suppressed_no_sink")
+        .entered();
+    let (lexer, _ctx, _errors, _source) = build_test_lexer("[ab]");
+    let ctx = Context::empty();
+    use AbcToken::*;
+
+    let failure = ctx.suppressed(|ctx| center(
+            one(OpenBracket),
+            recover(sub(pattern), recover_before(CloseBracket)),
+            stabilize(one(CloseBracket)))
+        (lexer.clone(), ctx));
+
+    assert!(failure.is_err());
+}
+
+
+/// Test `expect_or_insert` combinator inserting a missing close bracket.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_expect_or_insert -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_expect_or_insert() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_expect_or_insert")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[abc");
+    use AbcToken::*;
+
+    let (value, succ) = both(
+            right(one(OpenBracket), pattern),
+            expect_or_insert(CloseBracket, "unclosed bracket"))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (
+        Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(4, 0, 4)),
+        }),
+        Span::at(Pos::new(4, 0, 4)));
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(4, 0, 4));
+    assert_eq!(errors.read().unwrap().len(), 1);
+    assert_eq!(errors.read().unwrap()[0].message(), "unclosed bracket");
+}
+
+
+/// Test failed `center` combinator with error recovery, using
+/// `parse_collecting`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_collecting -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover_collecting() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_center_recover_collecting")
+        .entered();
+    let (lexer, _ctx, _errors, _source) = build_test_lexer("[ab]");
+    use AbcToken::*;
+
+    let (value, errors) = parse_collecting(lexer, center(
+        one(OpenBracket),
+        recover(sub(pattern), recover_before(CloseBracket)),
+        stabilize(one(CloseBracket))));
+
+    assert_eq!(value, Some(None));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(format!("{}", errors[0]), "\
+error: expected pattern
+ --> (0:0-0:4, bytes 0-4)
+  | 
+0 | [ab]
+  |  ^^ expected 'ABC', 'BXX', or 'XYC' pattern
+");
+}
+
+
+/// Test failed `both` combinator with two recovered errors, using
+/// `parse_continuing`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both_recover_continuing -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_both_recover_continuing() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_both_recover_continuing")
+        .entered();
+    let (lexer, _ctx, _errors, _source) = build_test_lexer("[ab] [ab]");
+    use AbcToken::*;
+
+    let failure = parse_continuing(lexer, both(
+            center(
+                one(OpenBracket),
+                recover(sub(pattern), recover_before(CloseBracket)),
+                stabilize(one(CloseBracket))),
+            center(
+                one(OpenBracket),
+                recover(sub(pattern), recover_before(CloseBracket)),
+                stabilize(one(CloseBracket)))))
+        .expect_err("failed parse");
+
+    assert_eq!(failure.len(), 2);
+    let errors: Vec<_> = failure
+        .iter_errors()
+        .map(|e| format!("{e}"))
+        .collect();
+    assert_eq!(errors, vec![
+"\
+error: expected pattern
+ --> (0:0-0:9, bytes 0-9)
+  | 
+0 | [ab] [ab]
+  |  ^^ expected 'ABC', 'BXX', or 'XYC' pattern
+".to_string(),
+"\
+error: expected pattern
+ --> (0:0-0:9, bytes 0-9)
+  | 
+0 | [ab] [ab]
+  |       ^^ expected 'ABC', 'BXX', or 'XYC' pattern
+".to_string(),
+    ]);
+}
+
+
+/// Test failed `center` combinator with error recovery, with a delayed close
+/// center.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_delayed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover_delayed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_center_recover_delayed")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab   bbb] ");
+    use AbcToken::*;
+
+    let (value, succ) = center(
+            one(OpenBracket),
+            recover(sub(pattern), recover_before(CloseBracket)),
+            stabilize(one(CloseBracket)))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = None;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(10, 0, 10));
+
+    assert_eq!(errors.read().unwrap().len(), 1);
+    assert_eq!(format!("{}", errors.write().unwrap().pop().unwrap()), "\
+error: expected pattern
+ --> (0:0-0:11, bytes 0-11)
+  | 
+0 | [ab   bbb] 
+  |  ^^^^^^ expected 'ABC', 'BXX', or 'XYC' pattern
+");
+}
+
+
+/// Test failed `center` combinator with error recovery, where the center parse
+/// is unrecoverable. Ensure the error is not recovered.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_unrecoverable -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover_unrecoverable() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_center_recover_unrecoverable")
+        .entered();
+    let (lexer, ctx, errors, source) = build_test_lexer("[ab]");
+    use AbcToken::*;
+
+    let actual = recover(
+            center(
+                one(OpenBracket),
+                unrecoverable(sub(pattern)),
+                one(CloseBracket)),
+            recover_before(CloseBracket))
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(errors.read().unwrap().len(), 0);
+    assert_eq!(format!("{actual}"), "\
+error: expected pattern
+ --> (0:0-0:4, bytes 0-4)
+  | 
+0 | [ab]
+  |  ^^ expected 'ABC', 'BXX', or 'XYC' pattern
+");
+}
+
+
+/// Test `either` combinator with error recovery, where the first alternative
+/// is unrecoverable. Ensure the first alternative's error is not recovered, and
+/// error recovery is re-enabled for the second alternative.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_either_unrecoverable_then_recover -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_either_unrecoverable_then_recover() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_either_unrecoverable_then_recover")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab]");
+    use AbcToken::*;
+
+    let (value, succ) = either(
+            recover(
+                unrecoverable(center(
+                    one(OpenBracket),
+                    sub(pattern),
+                    one(CloseBracket))),
+                recover_before(CloseBracket)),
+            recover(
+                center(
+                    one(OpenBracket),
+                    sub(pattern),
+                    one(CloseBracket)),
+                recover_before(CloseBracket)))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = None;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+    assert_eq!(errors.read().unwrap().len(), 1);
+}
+
+
+/// Test failed `both` combinator with recovered errors exceeding the
+/// `Context`'s maximum error count. Ensure the parse is aborted.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_both_recover_error_limit -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_both_recover_error_limit() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_both_recover_error_limit")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab] [ab] [ab]");
+    let ctx = ctx.with_max_total_errors(2);
+    use AbcToken::*;
+
+    let failure = both(
+            both(
+                center(
+                    one(OpenBracket),
+                    recover(sub(pattern), recover_before(CloseBracket)),
+                    stabilize(one(CloseBracket))),
+                center(
+                    one(OpenBracket),
+                    recover(sub(pattern), recover_before(CloseBracket)),
+                    stabilize(one(CloseBracket)))),
+            center(
+                one(OpenBracket),
+                recover(sub(pattern), recover_before(CloseBracket)),
+                stabilize(one(CloseBracket))))
+        (lexer.clone(), ctx)
+        .unwrap_err();
+
+    assert!(!failure.is_recoverable());
+    assert_eq!(format!("{failure}"),
+        "too many errors; aborting after 2 errors");
+    assert_eq!(errors.read().unwrap().len(), 2);
+}
+
+
+/// Test successful parse which pushes a warning to the `Context`. Ensure the
+/// warning is sent to the warning sink rather than the error sink.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_one_push_warning -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn simple_one_push_warning() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "simple_one_push_warning")
+        .entered();
+    let (lexer, ctx, errors, source) = build_test_lexer("a");
+    let warnings = Rc::new(RwLock::new(Vec::new()));
+    let ctx_warnings = warnings.clone();
+    let ctx = ctx.with_warning_sink(Box::new(move |e|
+        ctx_warnings.write().unwrap().push(e.into_source_error(source))
+    ));
+    use AbcToken::*;
+
+    let deprecated = |
+        lexer: Lexer<'static, Abc>,
+        ctx: Context<'static, Abc>|
+        -> ParseResult<'static, Abc, AbcToken>
+    {
+        let succ = one(A)(lexer, ctx.clone())?;
+        ctx.push_warning(Box::new(FailureError {
+            error_span: succ.lexer.token_span(),
+            message: "deprecated syntax".to_owned(),
+        }));
+        Ok(succ)
+    };
+
+    let (value, succ) = deprecated(lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, A);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(1, 0, 1));
+    assert!(errors.read().unwrap().is_empty());
+
+    let warnings = warnings.read().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(format!("{}", warnings[0]), "\
+warning: deprecated syntax
+ --> (0:0-0:1, bytes 0-1)
+  | 
+0 | a
+  | ^ deprecated syntax
+");
+}
+
+
+/// Test `Context::child_scope` grouping errors from independent parses.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::simple_child_scope -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_both() {
+fn simple_child_scope() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_both")
+    let _trace_span = span!(Level::DEBUG, "simple_child_scope").entered();
+    let (lexer, ctx, errors, source) = build_test_lexer("a b");
+    use AbcToken::*;
+
+    let flagged = |token, count| move |
+        lexer: Lexer<'static, Abc>,
+        ctx: Context<'static, Abc>|
+        -> ParseResult<'static, Abc, AbcToken>
+    {
+        let succ = one(token)(lexer, ctx.clone())?;
+        for _ in 0..count {
+            ctx.send_error(Box::new(FailureError {
+                error_span: succ.lexer.token_span(),
+                message: "flagged token".to_owned(),
+            }))?;
+        }
+        Ok(succ)
+    };
+
+    let (ctx_a, scope_a) = ctx.child_scope();
+    let succ = flagged(A, 2)(lexer, ctx_a).expect("successful parse");
+    let (ctx_b, scope_b) = ctx.child_scope();
+    let succ = flagged(B, 1)(succ.lexer, ctx_b).expect("successful parse");
+
+    assert_eq!(succ.value, B);
+    assert_eq!(scope_a.len(), 2);
+    assert_eq!(scope_b.len(), 1);
+    assert!(errors.read().unwrap().is_empty());
+
+    scope_b.forward_to(&ctx).expect("forward to error sink");
+
+    assert!(scope_b.is_empty());
+    let actual = errors.read().unwrap();
+    assert_eq!(actual.len(), 1);
+    assert_eq!(format!("{}", actual[0]), "\
+error: flagged token
+ --> (0:0-0:3, bytes 0-3)
+  | 
+0 | a b
+  |   ^ flagged token
+");
+
+    let first = scope_a.take_errors()
+        .into_iter()
+        .next()
+        .expect("scoped error");
+    assert_eq!(format!("{}", first.into_source_error(source)), "\
+error: flagged token
+ --> (0:0-0:3, bytes 0-3)
+  | 
+0 | a b
+  | ^ flagged token
+");
+}
+
+
+/// Test failed `center` combinator with error recovery, where the center parse
+/// is unrecoverable and the `Context` collects committed errors. Ensure the
+/// error is recovered outside of the unrecoverable parse.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_unrecoverable_collect -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_center_recover_unrecoverable_collect() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_center_recover_unrecoverable_collect")
         .entered();
-    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab]");
+    let ctx = ctx.with_commit_policy(CommitPolicy::Collect);
+    use AbcToken::*;
 
-    let (value, succ) = both(pattern, pattern)
+    let (value, succ) = recover(
+            center(
+                one(OpenBracket),
+                unrecoverable(sub(pattern)),
+                one(CloseBracket)),
+            recover_before(CloseBracket))
         (lexer.clone(), ctx)
         .expect("successful parse")
         .take_value();
 
     let actual = value;
-    let expected = (
+    let expected = None;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+
+    assert_eq!(errors.read().unwrap().len(), 1);
+    assert_eq!(format!("{}", errors.write().unwrap().pop().unwrap()), "\
+error: expected pattern
+ --> (0:0-0:4, bytes 0-4)
+  | 
+0 | [ab]
+  |  ^^ expected 'ABC', 'BXX', or 'XYC' pattern
+");
+}
+
+
+/// Test successful `section` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_section -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_section() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_section")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[abc]");
+    use AbcToken::*;
+
+    let (value, succ) = section(
+            one(OpenBracket),
+            left(sub(pattern), one(CloseBracket)),
+            recover_before(CloseBracket))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Some((
+        OpenBracket,
         Pattern::Abc(Spanned {
             value: "abc",
-            span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
-        }),
-        Pattern::Xyc(Spanned {
-            value: "dac",
-            span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7)),
+            span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(4, 0, 4)),
         }),
-    );
+    ));
 
     assert_eq!(actual, expected);
-    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
+    assert_eq!(errors.read().unwrap().len(), 0);
 }
 
-
-/// Test successful `left` combinator.
+/// Test failed `section` combinator. Ensure the error is collected at the
+/// section boundary.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_left -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_section_recover -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_left() {
+fn pattern_section_recover() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_left")
+    let _trace_span = span!(Level::DEBUG, "pattern_section_recover")
         .entered();
-    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab]");
+    use AbcToken::*;
 
-    let (value, succ) = left(pattern, pattern)
+    let (value, succ) = section(
+            one(OpenBracket),
+            left(sub(pattern), one(CloseBracket)),
+            recover_before(CloseBracket))
         (lexer.clone(), ctx)
         .expect("successful parse")
         .take_value();
 
-
     let actual = value;
-    let expected = Pattern::Abc(Spanned {
-        value: "abc",
-        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
-    });
+    let expected = None;
 
     assert_eq!(actual, expected);
-    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+
+    assert_eq!(errors.read().unwrap().len(), 1);
+    assert_eq!(format!("{}", errors.write().unwrap().pop().unwrap()), "\
+error: expected pattern
+ --> (0:0-0:4, bytes 0-4)
+  | 
+0 | [ab]
+  |  ^^ expected 'ABC', 'BXX', or 'XYC' pattern
+");
 }
 
+/// Test failed `section` combinator without a recovery point. Ensure the
+/// failure is committed.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_section_committed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_section_committed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_section_committed")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[ab");
+    use AbcToken::*;
 
-/// Test successful `right` combinator.
+    let failure = section(
+            one(OpenBracket),
+            left(sub(pattern), one(CloseBracket)),
+            recover_before(CloseBracket))
+        (lexer.clone(), ctx)
+        .unwrap_err();
+
+    assert!(failure.is_committed());
+    assert_eq!(errors.read().unwrap().len(), 1);
+}
+
+
+/// Test successful `fold_left` combinator.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_right -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::fold_left_three -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_right() {
+fn fold_left_three() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_right")
+    let _trace_span = span!(Level::DEBUG, "fold_left_three")
         .entered();
-    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a,b,c");
+    use AbcToken::*;
 
-    let (value, succ) = right(pattern, pattern)
+    let (value, succ) = fold_left(
+            map(text(any(&[A, B, C])), String::from),
+            one(Comma),
+            |l, _, r| format!("({l}, {r})"))
         (lexer.clone(), ctx)
         .expect("successful parse")
         .take_value();
 
+    let actual = value;
+    let expected = "((a, b), c)".to_string();
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
+}
+
+
+/// Test successful `fold_right` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::fold_right_three -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn fold_right_three() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "fold_right_three")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a,b,c");
+    use AbcToken::*;
+
+    let (value, succ) = fold_right(
+            map(text(any(&[A, B, C])), String::from),
+            one(Comma),
+            |l, _, r| format!("({l}, {r})"))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
 
     let actual = value;
-    let expected = Pattern::Xyc(Spanned {
-        value: "dac",
-        span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7)),
-    });
+    let expected = "(a, (b, c))".to_string();
 
     assert_eq!(actual, expected);
-    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
 }
 
 
-/// Test `right` combinator failure. Ensure error is properly wrapped.
+/// Test failed `fold_left` combinator with a committed operator failure.
+/// Ensure the failure is returned rather than ending the sequence.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_right_failed -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::fold_left_operator_committed -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_right_failed() {
+fn fold_left_operator_committed() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_right_failed")
+    let _trace_span = span!(Level::DEBUG, "fold_left_operator_committed")
         .entered();
-    let (lexer, ctx, _errors, source) = build_test_lexer("abc ddd");
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a,;b,c");
+    use AbcToken::*;
 
-    let actual = right(pattern, sub(pattern))
+    let failure = fold_left(
+            map(text(any(&[A, B, C])), String::from),
+            commit_after(one(Comma), one(Semicolon)),
+            |l, _, r| format!("({l}, {r})"))
         (lexer.clone(), ctx)
-        .map_err(|e| e.into_source_error(source))
         .unwrap_err();
 
-    assert_eq!(format!("{actual}"), "\
-error: expected pattern
- --> (0:0-0:7, bytes 0-7)
-  | 
-0 | abc ddd
-  |     ^^^ expected 'ABC', 'BXX', or 'XYC' pattern
-");
+    assert!(failure.is_committed());
 }
 
 
-/// Test failed `right` combinator with `raw` wrapper. Ensure error is not
-/// wrapped.
+/// Test failed `fold_right` combinator with a committed operator failure.
+/// Ensure the failure is returned rather than ending the sequence.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_right_failed_raw -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::fold_right_operator_committed -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_right_failed_raw() {
+fn fold_right_operator_committed() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_right_failed_raw")
+    let _trace_span = span!(Level::DEBUG, "fold_right_operator_committed")
         .entered();
-    let (lexer, ctx, _errors, source) = build_test_lexer("abc ddd");
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a,;b,c");
+    use AbcToken::*;
 
-    let actual = raw(right(pattern, pattern))
+    let failure = fold_right(
+            map(text(any(&[A, B, C])), String::from),
+            commit_after(one(Comma), one(Semicolon)),
+            |l, _, r| format!("({l}, {r})"))
         (lexer.clone(), ctx)
-        .map_err(|e| e.into_source_error(source))
         .unwrap_err();
 
-    assert_eq!(format!("{actual}"), "\
-error: unexpected token
- --> (0:0-0:7, bytes 0-7)
-  | 
-0 | abc ddd
-  |       ^ expected 'c'; found 'd'
-");
+    assert!(failure.is_committed());
 }
 
 
-/// Test successful `center` combinator.
+/// Test successful `fold_left` combinator with no operator.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::fold_left_single -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_center() {
+fn fold_left_single() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_center")
+    let _trace_span = span!(Level::DEBUG, "fold_left_single")
         .entered();
-    let (lexer, ctx, _errors, _source) = build_test_lexer("[abc]");
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a b");
     use AbcToken::*;
 
-    let (value, succ) = center(
-            one(OpenBracket),
-            pattern,
-            one(CloseBracket))
+    let (value, succ) = fold_left(
+            map(text(any(&[A, B, C])), String::from),
+            one(Comma),
+            |l, _, r| format!("({l}, {r})"))
         (lexer.clone(), ctx)
         .expect("successful parse")
         .take_value();
 
     let actual = value;
-    let expected = Pattern::Abc(Spanned {
-        value: "abc",
-        span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(4, 0, 4)),
-    });
+    let expected = "a".to_string();
 
     assert_eq!(actual, expected);
-    assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(1, 0, 1));
 }
 
 
-/// Test failed `center` combinator with error recovery.
+/// Test failed `commit_after` combinator within a `maybe` combinator, where
+/// the prefix succeeds. Ensure the committed failure is returned.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_commit_after_maybe -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_center_recover() {
+fn pattern_commit_after_maybe() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_center_recover")
+    let _trace_span = span!(Level::DEBUG, "pattern_commit_after_maybe")
         .entered();
-    let (lexer, ctx, errors, _source) = build_test_lexer("[ab]");
+    let (lexer, ctx, _errors, source) = build_test_lexer("[xx");
     use AbcToken::*;
 
-    let (value, succ) = center(
-            one(OpenBracket),
-            recover(sub(pattern), recover_before(CloseBracket)),
-            stabilize(one(CloseBracket)))
+    let failure = maybe(commit_after(one(OpenBracket), pattern))
+        (lexer.clone(), ctx)
+        .unwrap_err();
+
+    assert!(failure.is_committed());
+    assert_eq!(failure.into_source_error(source).message(),
+        "expected pattern");
+}
+
+
+/// Test failed `commit_after` combinator within a `maybe` combinator, where
+/// the prefix fails. Ensure no failure is returned.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_commit_after_maybe_prefix_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_commit_after_maybe_prefix_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "pattern_commit_after_maybe_prefix_failed")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("xx]");
+    use AbcToken::*;
+
+    let (value, succ) = maybe(commit_after(one(OpenBracket), pattern))
         (lexer.clone(), ctx)
         .expect("successful parse")
         .take_value();
@@ -352,53 +2184,77 @@ fn pattern_center_recover() {
     let expected = None;
 
     assert_eq!(actual, expected);
-    assert_eq!(succ.lexer.cursor_pos(), Pos::new(4, 0, 4));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(0, 0, 0));
+}
 
-    assert_eq!(errors.read().unwrap().len(), 1);
-    assert_eq!(format!("{}", errors.write().unwrap().pop().unwrap()), "\
-error: expected pattern
- --> (0:0-0:4, bytes 0-4)
-  | 
-0 | [ab]
-  |  ^^ expected 'ABC', 'BXX', or 'XYC' pattern
-");
+
+/// Test failed `commit_after` combinator within an `either` combinator, where
+/// the prefix succeeds. Ensure the alternative is not attempted.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_commit_after_either -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn pattern_commit_after_either() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "pattern_commit_after_either")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("[xx");
+    use AbcToken::*;
+
+    let failure = either(
+            right(commit_after(one(OpenBracket), pattern), empty),
+            right(one(OpenBracket), empty))
+        (lexer.clone(), ctx)
+        .unwrap_err();
+
+    assert!(failure.is_committed());
 }
 
 
-/// Test failed `center` combinator with error recovery, with a delayed close
-/// center.
+/// Test `center` combinator with error recovery following an `unambiguous`
+/// combinator. Ensure the error sink is restored after `unambiguous`.
 //
 // To collect trace output:
-// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_center_recover_delayed -- --exact --nocapture > .trace
+// RUST_LOG=TRACE cargo test --all-features test::abc_join::pattern_unambiguous_then_recover -- --exact --nocapture > .trace
 #[test]
 #[timeout(100)]
-fn pattern_center_recover_delayed() {
+fn pattern_unambiguous_then_recover() {
     let _trace_guard = setup_test_environment();
-    let _trace_span = span!(Level::DEBUG, "pattern_center_recover_delayed")
+    let _trace_span = span!(Level::DEBUG, "pattern_unambiguous_then_recover")
         .entered();
-    let (lexer, ctx, errors, _source) = build_test_lexer("[ab   bbb] ");
+    let (lexer, ctx, errors, _source) = build_test_lexer("abc [ab]");
     use AbcToken::*;
 
-    let (value, succ) = center(
-            one(OpenBracket),
-            recover(sub(pattern), recover_before(CloseBracket)),
-            stabilize(one(CloseBracket)))
+    let mut abc = text(pattern);
+    let mut bc = text(seq(&[B, C]));
+    let mut parsers: [&mut dyn FnMut(
+            Lexer<'static, Abc>,
+            Context<'static, Abc>)
+        -> ParseResult<'static, Abc, &'static str>; 2] = [&mut abc, &mut bc];
+
+    let (value, succ) = both(
+            unambiguous(&mut parsers[..]),
+            center(
+                one(OpenBracket),
+                recover(sub(pattern), recover_before(CloseBracket)),
+                one(CloseBracket)))
         (lexer.clone(), ctx)
         .expect("successful parse")
         .take_value();
 
     let actual = value;
-    let expected = None;
+    let expected = ("abc", None);
 
     assert_eq!(actual, expected);
-    assert_eq!(succ.lexer.cursor_pos(), Pos::new(10, 0, 10));
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(8, 0, 8));
 
     assert_eq!(errors.read().unwrap().len(), 1);
     assert_eq!(format!("{}", errors.write().unwrap().pop().unwrap()), "\
 error: expected pattern
- --> (0:0-0:11, bytes 0-11)
+ --> (0:0-0:8, bytes 0-8)
   | 
-0 | [ab   bbb] 
-  |  ^^^^^^ expected 'ABC', 'BXX', or 'XYC' pattern
+0 | abc [ab]
+  |      ^^ expected 'ABC', 'BXX', or 'XYC' pattern
 ");
 }
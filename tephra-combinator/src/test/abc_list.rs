@@ -9,9 +9,25 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Internal library imports.
+use crate::any;
 use crate::bracket_default_index;
+use crate::commit_after;
+use crate::count;
+use crate::dotted_path;
+use crate::intersperse_count_until;
+use crate::intersperse_fold;
 use crate::list;
 use crate::list_bounded;
+use crate::list_spanned;
+use crate::list_with_options;
+use crate::ListOptions;
+use crate::maybe;
+use crate::one;
+use crate::repeat;
+use crate::separated_nonempty_list;
+use crate::take_until;
+use crate::take_while;
+use crate::text;
 use crate::test::abc::Abc;
 use crate::test::abc::AbcToken;
 use crate::test::abc::pattern;
@@ -177,6 +193,206 @@ fn bracket_list_one() {
     assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
 }
 
+
+/// Test successful `list_with_options` combinator with an allowed trailing
+/// separator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::list_with_options_trailing_allowed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn list_with_options_trailing_allowed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "list_with_options_trailing_allowed")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[abc,abc,]");
+    use AbcToken::*;
+
+    let (value, succ) = bracket_default_index(
+            &[OpenBracket],
+            list_with_options(
+                pattern,
+                Comma,
+                |tok| *tok == CloseBracket,
+                ListOptions { allow_trailing: true, allow_leading: false }),
+            &[CloseBracket],
+            |_| false)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (vec![
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(4, 0, 4)),
+        })),
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(5, 0, 5), Pos::new(8, 0, 8)),
+        })),
+        ],
+        0);
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(10, 0, 10));
+    assert_eq!(errors.read().unwrap().len(), 0);
+}
+
+
+/// Test `list_with_options` combinator with a rejected trailing separator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::list_with_options_trailing_rejected -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn list_with_options_trailing_rejected() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "list_with_options_trailing_rejected")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[abc,abc,]");
+    use AbcToken::*;
+
+    let (value, succ) = bracket_default_index(
+            &[OpenBracket],
+            list_with_options(
+                pattern,
+                Comma,
+                |tok| *tok == CloseBracket,
+                ListOptions { allow_trailing: false, allow_leading: false }),
+            &[CloseBracket],
+            |_| false)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (vec![
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(4, 0, 4)),
+        })),
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(5, 0, 5), Pos::new(8, 0, 8)),
+        })),
+        ],
+        0);
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(10, 0, 10));
+    assert_eq!(errors.read().unwrap().len(), 1);
+    assert_eq!(format!("{}", errors.read().unwrap()[0]), "\
+error: unexpected trailing separator
+ --> (0:0-0:10, bytes 0-10)
+  | 
+0 | [abc,abc,]
+  |         ^ separator not permitted here
+");
+}
+
+
+/// Test successful `list_with_options` combinator with an allowed leading
+/// separator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::list_with_options_leading_allowed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn list_with_options_leading_allowed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "list_with_options_leading_allowed")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[,abc,abc]");
+    use AbcToken::*;
+
+    let (value, succ) = bracket_default_index(
+            &[OpenBracket],
+            list_with_options(
+                pattern,
+                Comma,
+                |tok| *tok == CloseBracket,
+                ListOptions { allow_trailing: false, allow_leading: true }),
+            &[CloseBracket],
+            |_| false)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (vec![
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(2, 0, 2), Pos::new(5, 0, 5)),
+        })),
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(6, 0, 6), Pos::new(9, 0, 9)),
+        })),
+        ],
+        0);
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(10, 0, 10));
+    assert_eq!(errors.read().unwrap().len(), 0);
+}
+
+
+/// Test `list_with_options` combinator with a rejected leading separator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::list_with_options_leading_rejected -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn list_with_options_leading_rejected() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "list_with_options_leading_rejected")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("[,abc,abc]");
+    use AbcToken::*;
+
+    let (value, succ) = bracket_default_index(
+            &[OpenBracket],
+            list_with_options(
+                pattern,
+                Comma,
+                |tok| *tok == CloseBracket,
+                ListOptions { allow_trailing: true, allow_leading: false }),
+            &[CloseBracket],
+            |_| false)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = (vec![
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(2, 0, 2), Pos::new(5, 0, 5)),
+        })),
+        Some(Pattern::Abc(Spanned {
+            value: "abc",
+            span: Span::enclosing(Pos::new(6, 0, 6), Pos::new(9, 0, 9)),
+        })),
+        ],
+        0);
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(10, 0, 10));
+    assert_eq!(errors.read().unwrap().len(), 1);
+    assert_eq!(format!("{}", errors.read().unwrap()[0]), "\
+error: unexpected leading separator
+ --> (0:0-0:10, bytes 0-10)
+  | 
+0 | [,abc,abc]
+  |  ^ separator not permitted here
+");
+}
+
 /// Test successful `list_bounded` combinator.
 //
 // To collect trace output:
@@ -642,3 +858,497 @@ error: expected pattern
   |      \\ expected 'ABC', 'BXX', or 'XYC' pattern
 ");
 }
+
+
+/// Test failed `separated_nonempty_list` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::nonempty_list_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn nonempty_list_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "nonempty_list_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("  ");
+    use AbcToken::*;
+
+    let actual = unrecoverable(
+        separated_nonempty_list(
+            pattern,
+            Comma, |_| false))
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: empty list
+ --> (0:0-0:2, bytes 0-2)
+  | 
+0 |   
+  |   \\ expected at least one item
+");
+}
+
+
+/// Test successful `intersperse_count_until` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::intersperse_count_until_stop -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn intersperse_count_until_stop() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "intersperse_count_until_stop")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc, bdd, abc;");
+    use AbcToken::*;
+
+    let (value, succ) = intersperse_count_until(0, None,
+            one(Semicolon),
+            pattern,
+            one(Comma))
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = 3;
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(13, 0, 13));
+}
+
+
+/// Test successful `count` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::count_three -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn count_three() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "count_three")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("aaa");
+    use AbcToken::*;
+
+    let (value, succ) = count(2, Some(4), one(A))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = vec![A, A, A];
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+}
+
+
+/// Test `count` combinator stopping at the maximum count.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::count_max -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn count_max() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "count_max")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("aaaaa");
+    use AbcToken::*;
+
+    let (value, succ) = count(2, Some(4), one(A))
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = vec![A, A, A, A];
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(4, 0, 4));
+}
+
+
+/// Test failed `count` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::count_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn count_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "count_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("a");
+    use AbcToken::*;
+
+    let actual = unrecoverable(count(2, Some(4), one(A)))
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: invalid item count
+ --> (0:0-0:1, bytes 0-1)
+  | 
+0 | a
+  | ^ expected 2 items; found 1
+");
+}
+
+
+/// Test failed `count` combinator with a committed failure. Ensure the failure
+/// is returned rather than ending the repetition.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::count_committed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn count_committed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "count_committed")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a b a c");
+    use AbcToken::*;
+
+    let failure = count(0, None, commit_after(one(A), one(B)))
+        (lexer.clone(), ctx)
+        .unwrap_err();
+
+    assert!(failure.is_committed());
+}
+
+
+/// Test successful `intersperse_fold` combinator stopping at a terminator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::intersperse_fold_terminated -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn intersperse_fold_terminated() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "intersperse_fold_terminated")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a, b, c; d");
+    use AbcToken::*;
+
+    let (value, succ) = intersperse_fold(
+            String::new(),
+            text(any(&[A, B, C])),
+            one(Comma),
+            |tok| *tok == Semicolon,
+            |mut acc, s| { acc.push_str(s); acc })
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = "abc".to_string();
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(7, 0, 7));
+}
+
+
+/// Test failed `intersperse_fold` combinator with a committed failure of the
+/// secondary parser. Ensure the failure is returned rather than ending the
+/// sequence.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::intersperse_fold_committed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn intersperse_fold_committed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "intersperse_fold_committed")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a,b");
+    use AbcToken::*;
+
+    let failure = intersperse_fold(
+            String::new(),
+            text(any(&[A, B, C])),
+            commit_after(one(Comma), one(Semicolon)),
+            |tok| *tok == D,
+            |mut acc, s| { acc.push_str(s); acc })
+        (lexer.clone(), ctx)
+        .unwrap_err();
+
+    assert!(failure.is_committed());
+}
+
+
+/// Test `intersperse_fold` combinator with no items before the terminator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::intersperse_fold_empty -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn intersperse_fold_empty() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "intersperse_fold_empty")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("; a");
+    use AbcToken::*;
+
+    let (value, succ) = intersperse_fold(
+            0,
+            one(A),
+            one(Comma),
+            |tok| *tok == Semicolon,
+            |acc, _| acc + 1)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, 0);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(0, 0, 0));
+}
+
+
+/// Test `repeat` combinator stopping on a non-advancing parse.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::repeat_non_advancing -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn repeat_non_advancing() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "repeat_non_advancing")
+        .entered();
+    let (lexer, ctx, errors, _source) = build_test_lexer("a a b");
+    use AbcToken::*;
+
+    let (value, succ) = repeat(0, None, maybe(one(A)))
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = vec![Some(A), Some(A)];
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(3, 0, 3));
+    if cfg!(debug_assertions) {
+        assert_eq!(errors.read().unwrap().len(), 1);
+        assert_eq!(errors.read().unwrap()[0].message(),
+            "non-advancing repetition");
+    } else {
+        assert!(errors.read().unwrap().is_empty());
+    }
+}
+
+
+/// Test successful `list_spanned` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::list_spanned_two -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn list_spanned_two() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "list_spanned_two")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc, bdd");
+    use AbcToken::*;
+
+    let (value, succ) = list_spanned(
+            pattern,
+            Comma, |_| false)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: vec![
+            Some(Pattern::Abc(Spanned {
+                value: "abc",
+                span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3)),
+            })),
+            Some(Pattern::Bxx(Spanned {
+                value: "bdd",
+                span: Span::enclosing(Pos::new(5, 0, 5), Pos::new(8, 0, 8)),
+            })),
+        ],
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(8, 0, 8)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(8, 0, 8));
+}
+
+/// Test successful `list_spanned` combinator with empty list.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::list_spanned_empty -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn list_spanned_empty() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "list_spanned_empty")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("");
+    use AbcToken::*;
+
+    let (value, succ) = list_spanned(
+            pattern,
+            Comma, |_| false)
+        (lexer.clone(), ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: vec![],
+        span: Span::at(Pos::new(0, 0, 0)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(0, 0, 0));
+}
+
+
+/// Test successful `dotted_path` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::dotted_path_three -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn dotted_path_three() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "dotted_path_three")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a,a,a b");
+    use AbcToken::*;
+
+    let (value, succ) = dotted_path(A, Comma)
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: vec![
+            Spanned {
+                value: "a",
+                span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(1, 0, 1)),
+            },
+            Spanned {
+                value: "a",
+                span: Span::enclosing(Pos::new(2, 0, 2), Pos::new(3, 0, 3)),
+            },
+            Spanned {
+                value: "a",
+                span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(5, 0, 5)),
+            },
+        ],
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(5, 0, 5)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(5, 0, 5));
+}
+
+
+/// Test failed `dotted_path` combinator with a trailing separator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::dotted_path_trailing_failed -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn dotted_path_trailing_failed() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "dotted_path_trailing_failed")
+        .entered();
+    let (lexer, ctx, _errors, source) = build_test_lexer("a,a,");
+    use AbcToken::*;
+
+    let actual = dotted_path(A, Comma)
+        (lexer, ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:4, bytes 0-4)
+  | 
+0 | a,a,
+  |    ^ expected 'a'; found end of text
+  = note: expected a path segment after the separator
+");
+}
+
+
+/// Test successful `take_until` combinator.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::take_until_comma -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn take_until_comma() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "take_until_comma")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a b  a, b");
+    use AbcToken::*;
+
+    let (value, succ) = take_until(|tok| *tok == Comma)
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: vec![
+            Spanned {
+                value: A,
+                span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(1, 0, 1)),
+            },
+            Spanned {
+                value: B,
+                span: Span::enclosing(Pos::new(2, 0, 2), Pos::new(3, 0, 3)),
+            },
+            Spanned {
+                value: A,
+                span: Span::enclosing(Pos::new(5, 0, 5), Pos::new(6, 0, 6)),
+            },
+        ],
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(6, 0, 6)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(6, 0, 6));
+}
+
+
+/// Test `take_while` combinator with no matching tokens.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_list::take_while_empty -- --exact --nocapture > .trace
+#[test]
+#[timeout(100)]
+fn take_while_empty() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "take_while_empty")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer(", a");
+    use AbcToken::*;
+
+    let (value, succ) = take_while(|tok| matches!(tok, A | B))
+        (lexer, ctx)
+        .expect("successful parse")
+        .take_value();
+
+    let actual = value;
+    let expected = Spanned {
+        value: vec![],
+        span: Span::at(Pos::new(0, 0, 0)),
+    };
+
+    assert_eq!(actual, expected);
+    assert_eq!(succ.lexer.cursor_pos(), Pos::new(0, 0, 0));
+}
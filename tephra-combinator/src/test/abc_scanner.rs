@@ -9,6 +9,9 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Internal library imports.
+use crate::end_of_text;
+use crate::memoize_bounded;
+use crate::take_while;
 use crate::test::abc::Abc;
 use crate::test::abc::AbcToken;
 use crate::test::abc::pattern;
@@ -17,11 +20,18 @@ use crate::test::abc::Pattern;
 // External library imports.
 use ntest::timeout;
 use pretty_assertions::assert_eq;
+use tephra::CodeDisplay;
 use tephra::Context;
 use tephra::error::SourceError;
+use tephra::error::SourceErrorOwned;
+use tephra::Highlight;
 use tephra::Lexer;
+use tephra::MessageType;
+use tephra::Note;
 use tephra::Pos;
+use tephra::Span;
 use tephra::SourceText;
+use tephra::SpanDisplay;
 use tephra::Spanned;
 use tephra_tracing::Level;
 use tephra_tracing::span;
@@ -33,6 +43,7 @@ use tracing_subscriber::layer::SubscriberExt as _;
 use tracing_subscriber::Registry;
 
 // Standard library imports.
+use std::cell::Cell;
 use std::rc::Rc;
 use std::sync::RwLock;
 
@@ -106,6 +117,127 @@ fn abc_tokens() {
     assert_eq!(actual, expected);
 }
 
+
+/// Tests `Lexer::iter_lexemes_with_text` with `Abc` tokens.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::abc_lexemes_with_text -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn abc_lexemes_with_text() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "abc_lexemes_with_text")
+        .entered();
+    let (mut lexer, _ctx, _errors, _source) = build_test_lexer("a b\nc");
+    use AbcToken::*;
+
+    let actual = lexer
+        .iter_lexemes_with_text()
+        .collect::<Vec<_>>();
+
+    let expected = vec![
+        (A, Span::enclosing(Pos::new(0, 0, 0), Pos::new(1, 0, 1)), "a"),
+        (B, Span::enclosing(Pos::new(2, 0, 2), Pos::new(3, 0, 3)), "b"),
+        (C, Span::enclosing(Pos::new(4, 1, 0), Pos::new(5, 1, 1)), "c"),
+    ];
+
+    assert_eq!(actual, expected);
+}
+
+
+/// Tests `Lexer::consumed_text` and `Lexer::remaining_text`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::abc_consumed_remaining_text -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn abc_consumed_remaining_text() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "abc_consumed_remaining_text")
+        .entered();
+    let (mut lexer, _ctx, _errors, _source) = build_test_lexer("abc def");
+    use AbcToken::*;
+
+    assert_eq!(lexer.next(), Some(A));
+    assert_eq!(lexer.next(), Some(B));
+    assert_eq!(lexer.next(), Some(C));
+
+    assert_eq!(lexer.consumed_text(), "abc");
+    assert_eq!(lexer.remaining_text(), " def");
+}
+
+
+/// Tests `Lexer::with_terminator`. Ensure the lexer stops before each
+/// terminator and `end_of_text` succeeds there.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::abc_terminated_statements -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn abc_terminated_statements() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "abc_terminated_statements")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("ab; ca");
+    use AbcToken::*;
+
+    let mut lexer = lexer.with_terminator(Some(Semicolon));
+
+    assert_eq!(lexer.next(), Some(A));
+    assert_eq!(lexer.next(), Some(B));
+    assert_eq!(lexer.peek(), None);
+    assert_eq!(lexer.next(), None);
+    assert!(lexer.is_at_terminator());
+
+    let succ = end_of_text(lexer, ctx.clone())
+        .expect("end of statement");
+    let mut lexer = succ.lexer;
+    assert_eq!(lexer.cursor_pos(), Pos::new(2, 0, 2));
+
+    let terminator = lexer.set_terminator(None);
+    assert_eq!(lexer.next(), Some(Semicolon));
+    let _ = lexer.set_terminator(terminator);
+
+    assert_eq!(lexer.next(), Some(C));
+    assert_eq!(lexer.next(), Some(A));
+    assert_eq!(lexer.next(), None);
+    assert!(!lexer.is_at_terminator());
+    assert!(end_of_text(lexer, ctx).is_ok());
+}
+
+
+/// Tests `Abc` tokens with wide and zero-width invalid characters.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::abc_tokens_unicode_width -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn abc_tokens_unicode_width() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "abc_tokens_unicode_width")
+        .entered();
+    let (mut lexer, _ctx, _errors, source)
+        = build_test_lexer("a\u{4E2D}b\u{301}c");
+    use AbcToken::*;
+
+    let actual = lexer
+        .iter_with_spans()
+        .map(|lex| (
+            lex.0,
+            format!("{:?} ({})", source.clipped(lex.1).as_str(), lex.1)))
+        .collect::<Vec<_>>();
+
+    let expected = vec![
+        (A,       "\"a\" (0:0-0:1, bytes 0-1)".to_string()),
+        (Invalid, "\"\u{4E2D}\" (0:1-0:3, bytes 1-4)".to_string()),
+        (B,       "\"b\" (0:3-0:4, bytes 4-5)".to_string()),
+        (Invalid, "\"\\u{301}\" (0:4, bytes 5-7)".to_string()),
+        (C,       "\"c\" (0:4-0:5, bytes 7-8)".to_string()),
+    ];
+
+    assert_eq!(actual, expected);
+}
+
 /// Parses a `Pattern::Abc`.
 //
 // To collect trace output:
@@ -213,3 +345,829 @@ error: expected pattern
   |     ^^^ expected 'ABC', 'BXX', or 'XYC' pattern
 ");
 }
+
+/// Ensures that a note pushed to the `Context` is attached to a failed parse.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::push_note_failed_pattern -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn push_note_failed_pattern() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "push_note_failed_pattern")
+        .entered();
+    let (lexer, mut ctx, _errors, source) = build_test_lexer("\n    aaa");
+    ctx.push_note(Note::new(MessageType::Help, "did you mean 'abc'?"));
+
+    let actual = pattern
+        (lexer.clone(), ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: expected pattern
+ --> (1:0-1:7, bytes 1-8)
+  | 
+1 |     aaa
+  |     ^^^ expected 'ABC', 'BXX', or 'XYC' pattern
+  = help: did you mean 'abc'?
+");
+}
+
+/// Ensures that the `Context` progress sink receives each consumed token
+/// position.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::progress_sink -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn progress_sink() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "progress_sink")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a b c");
+
+    let progress = Rc::new(RwLock::new(Vec::new()));
+    let ctx_progress = progress.clone();
+    let ctx = ctx.with_progress_sink(Box::new(move |pos|
+        ctx_progress.write().unwrap().push(pos)
+    ), 1);
+
+    let _ = pattern
+        (lexer.clone(), ctx)
+        .expect("successful parse");
+
+    let actual = progress.read().unwrap().clone();
+    let expected = vec![
+        Pos::new(1, 0, 1),
+        Pos::new(3, 0, 3),
+        Pos::new(5, 0, 5),
+    ];
+
+    assert_eq!(actual, expected);
+}
+
+/// Ensures that the `Context` progress sink receives each consumed token
+/// position once when a parse backtracks, and may use the `Context`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::progress_sink_backtrack -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn progress_sink_backtrack() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "progress_sink_backtrack")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("a b c");
+
+    let progress = Rc::new(RwLock::new(Vec::new()));
+    let ctx_progress = progress.clone();
+    let sink_ctx = ctx.clone();
+    let ctx = ctx.with_progress_sink(Box::new(move |pos| {
+        let _ = sink_ctx.commit_policy();
+        ctx_progress.write().unwrap().push(pos);
+    }), 1);
+
+    let _ = pattern
+        (lexer.clone(), ctx.clone())
+        .expect("successful parse");
+    let _ = take_while(|_| true)
+        (lexer.clone(), ctx)
+        .expect("successful parse");
+
+    let actual = progress.read().unwrap().clone();
+    let expected = vec![
+        Pos::new(1, 0, 1),
+        Pos::new(3, 0, 3),
+        Pos::new(5, 0, 5),
+    ];
+
+    assert_eq!(actual, expected);
+}
+
+/// Ensures that `Lexer::last_span` tracks the most recent non-filtered token.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::last_span -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn last_span() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "last_span")
+        .entered();
+    let (mut lexer, _ctx, _errors, _source) = build_test_lexer("a  b  ");
+
+    assert_eq!(lexer.last_span(), None);
+
+    let _ = lexer.next();
+    assert_eq!(lexer.last_span(), Some(Span::enclosing(
+        Pos::new(0, 0, 0),
+        Pos::new(1, 0, 1))));
+
+    let _ = lexer.next();
+    assert_eq!(lexer.last_span(), Some(Span::enclosing(
+        Pos::new(3, 0, 3),
+        Pos::new(4, 0, 4))));
+}
+
+/// Ensures that `Lexer::with_trivia` retains filtered trivia tokens, keyed by
+/// the position of the following token.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::retained_trivia -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn retained_trivia() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "retained_trivia")
+        .entered();
+    let (lexer, _ctx, _errors, _source) = build_test_lexer("a  b c");
+    use AbcToken::*;
+
+    let mut lexer = lexer.with_trivia(Some(Rc::new(|tok| *tok == Ws)));
+    let tokens: Vec<_> = lexer.by_ref().collect();
+    assert_eq!(tokens, vec![A, B, C]);
+
+    assert_eq!(lexer.trivia_before(Pos::new(3, 0, 3)), vec![
+        Spanned {
+            value: Ws,
+            span: Span::enclosing(Pos::new(1, 0, 1), Pos::new(3, 0, 3)),
+        },
+    ]);
+
+    let actual = lexer.take_trivia();
+    assert_eq!(actual.len(), 2);
+    assert_eq!(actual[&Pos::new(5, 0, 5)], vec![
+        Spanned {
+            value: Ws,
+            span: Span::enclosing(Pos::new(4, 0, 4), Pos::new(5, 0, 5)),
+        },
+    ]);
+    assert!(lexer.take_trivia().is_empty());
+}
+
+/// Ensures that `memoize_bounded` reuses memoized results and evicts the least
+/// recently used results past its capacity.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::memoize_bounded_eviction -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn memoize_bounded_eviction() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "memoize_bounded_eviction")
+        .entered();
+    let (lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+
+    let calls = Rc::new(Cell::new(0));
+    let pattern_calls = calls.clone();
+    let mut memo_pattern = memoize_bounded(1, "pattern", move |lx, cx| {
+        pattern_calls.set(pattern_calls.get() + 1);
+        pattern(lx, cx)
+    });
+    let mut memo_other = memoize_bounded(1, "other", pattern);
+
+    let first = memo_pattern(lexer.clone(), ctx.clone())
+        .expect("successful parse");
+    let second = memo_pattern(lexer.clone(), ctx.clone())
+        .expect("successful parse");
+
+    assert_eq!(calls.get(), 1);
+    assert_eq!(second.value, first.value);
+    assert_eq!(second.lexer.cursor_pos(), first.lexer.cursor_pos());
+    assert_eq!(second.lexer.parse_span(), first.lexer.parse_span());
+
+    // Results for other keys do not count toward the capacity.
+    let _ = memo_other(lexer.clone(), ctx.clone())
+        .expect("successful parse");
+    let _ = memo_pattern(lexer.clone(), ctx.clone())
+        .expect("successful parse");
+
+    assert_eq!(calls.get(), 1);
+
+    let _ = memo_pattern(first.lexer.clone(), ctx.clone())
+        .expect("successful parse");
+    let _ = memo_pattern(lexer, ctx)
+        .expect("successful parse");
+
+    assert_eq!(calls.get(), 3);
+}
+
+/// Ensures that a memoized result from `memoize_bounded` is applied to the
+/// given lexer, retaining the start of its parse span.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::memoize_bounded_applies_checkpoint -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn memoize_bounded_applies_checkpoint() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "memoize_bounded_applies_checkpoint")
+        .entered();
+    let (mut lexer, ctx, _errors, _source) = build_test_lexer("abc dac");
+    let _ = lexer.next();
+    let _ = lexer.next();
+    let _ = lexer.next();
+    let sub = lexer.clone().into_sublexer();
+
+    let mut memo_pattern = memoize_bounded(1, "pattern", pattern);
+    let first = memo_pattern(sub, ctx.clone())
+        .expect("successful parse");
+    let second = memo_pattern(lexer, ctx)
+        .expect("successful parse");
+
+    assert_eq!(first.lexer.parse_span(), Span::enclosing(
+        Pos::new(4, 0, 4),
+        Pos::new(7, 0, 7)));
+    assert_eq!(second.lexer.parse_span(), Span::enclosing(
+        Pos::new(0, 0, 0),
+        Pos::new(7, 0, 7)));
+    assert_eq!(second.lexer.cursor_pos(), first.lexer.cursor_pos());
+}
+
+
+/// Tests `SpanDisplay::with_render_tab_width`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::render_tab_width -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn render_tab_width() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "render_tab_width").entered();
+    let source = SourceText::new("\tabc");
+    let span = Span::enclosing(Pos::new(1, 0, 4), Pos::new(4, 0, 7));
+
+    let actual = SourceError::new(source, "tabbed text")
+        .with_span_display(SpanDisplay::new_error_highlight(
+                source,
+                span,
+                "highlighted")
+            .with_render_tab_width(4));
+
+    assert_eq!(format!("{actual}"), "\
+error: tabbed text
+ --> (0:0-0:7, bytes 0-4)
+  | 
+0 |     abc
+  |     ^^^ highlighted
+");
+
+    let actual = SourceError::new(source, "tabbed text")
+        .with_span_display(SpanDisplay::new_error_highlight(
+                source,
+                span,
+                "highlighted")
+            .with_render_tab_width(8));
+
+    assert_eq!(format!("{actual}"), "\
+error: tabbed text
+ --> (0:0-0:7, bytes 0-4)
+  | 
+0 |         abc
+  |         ^^^ highlighted
+");
+}
+
+
+/// Tests a single-line `Highlight` with both start and end messages.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::highlight_start_and_end_messages -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn highlight_start_and_end_messages() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "highlight_start_and_end_messages")
+        .entered();
+    let source = SourceText::new("abc def");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let actual = SourceError::new(source, "two messages")
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "end message")
+                .with_start_message("start message")
+                .with_error_type()));
+
+    assert_eq!(format!("{actual}"), "\
+error: two messages
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc def
+  |     ^^^ start message
+  |       | end message
+");
+}
+
+
+/// Tests a multi-line `Highlight` with its end message displayed below the
+/// highlighted lines.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::highlight_message_below -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn highlight_message_below() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "highlight_message_below")
+        .entered();
+    let source = SourceText::new("abc\ndef\nghi");
+    let span = Span::enclosing(Pos::new(0, 0, 0), Pos::new(6, 1, 2));
+
+    let actual = SourceError::new(source, "message below")
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "end message")
+                .with_message_below()
+                .with_error_type()));
+
+    assert_eq!(format!("{actual}"), "\
+error: message below
+ --> (0:0-1:3, bytes 0-7)
+  | 
+0 | / abc
+1 | | def
+  | |__^
+  |   end message
+");
+}
+
+/// Tests a `Highlight` with a custom underline glyph.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::highlight_underline_char -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn highlight_underline_char() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "highlight_underline_char")
+        .entered();
+    let source = SourceText::new("abc def");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let actual = SourceError::new(source, "custom underline")
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "underlined")
+                .with_error_type()
+                .with_underline_char('=')));
+
+    assert_eq!(format!("{actual}"), "\
+error: custom underline
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc def
+  |     === underlined
+");
+
+    let mut actual = String::new();
+    CodeDisplay::new("custom underline")
+        .with_error_type()
+        .with_color(true)
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "underlined")
+                .with_error_type()
+                .with_underline_char('=')))
+        .write(&mut actual, source)
+        .unwrap();
+
+    // The colored rendering path writes each glyph separately.
+    assert_eq!(actual.matches('=').count(), 3);
+    assert!(!actual.contains('^'));
+}
+
+
+/// Tests a `SpanDisplay` with a suggested replacement.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::span_display_suggested_replacement -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn span_display_suggested_replacement() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG,
+            "span_display_suggested_replacement")
+        .entered();
+    let source = SourceText::new("abc def\nghi");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let actual = SourceError::new(source, "unknown word")
+        .with_span_display(SpanDisplay::new_error_highlight(
+                source,
+                span,
+                "not a valid word")
+            .with_suggested_replacement(span, "deg"));
+
+    assert_eq!(format!("{actual}"), "\
+error: unknown word
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc def
+  |     ^^^ not a valid word
+  = suggestion:
+0 - abc def
+0 + abc deg
+");
+}
+
+
+/// Tests a `SpanDisplay` for a source text with a start position.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::span_display_start_position -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn span_display_start_position() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "span_display_start_position")
+        .entered();
+    let source = SourceText::new("abc def")
+        .with_start_position(Pos::new(200, 12, 0));
+    let span = Span::enclosing(Pos::new(204, 12, 4), Pos::new(207, 12, 7));
+
+    let actual = SourceError::new(source, "unknown word")
+        .with_span_display(SpanDisplay::new_error_highlight(
+            source,
+            span,
+            "not a valid word"));
+
+    assert_eq!(format!("{actual}"), "\
+error: unknown word
+  --> (12:0-12:7, bytes 200-207)
+   | 
+12 | abc def
+   |     ^^^ not a valid word
+");
+}
+
+
+/// Tests `SpanDisplay::with_mixed_indent_lint`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::mixed_indent_lint -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn mixed_indent_lint() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "mixed_indent_lint").entered();
+    let source = SourceText::new(" \tabc");
+    let span = Span::enclosing(Pos::new(2, 0, 4), Pos::new(5, 0, 7));
+
+    let actual = SourceError::new(source, "tabbed text")
+        .with_span_display(SpanDisplay::new_error_highlight(
+                source,
+                span,
+                "highlighted")
+            .with_render_tab_width(4)
+            .with_mixed_indent_lint(true));
+
+    assert_eq!(format!("{actual}"), "\
+error: tabbed text
+ --> (0:0-0:7, bytes 0-5)
+  | 
+0 |     abc
+  |     ^^^ highlighted
+  = note: line uses mixed tabs and spaces
+");
+
+    let actual = SourceError::new(source, "tabbed text")
+        .with_span_display(SpanDisplay::new_error_highlight(
+                source,
+                span,
+                "highlighted")
+            .with_render_tab_width(4));
+
+    assert_eq!(format!("{actual}"), "\
+error: tabbed text
+ --> (0:0-0:7, bytes 0-5)
+  | 
+0 |     abc
+  |     ^^^ highlighted
+");
+}
+
+
+/// Tests a `CodeDisplay` with the debug `MessageType`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::code_display_debug_type -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn code_display_debug_type() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "code_display_debug_type")
+        .entered();
+    let source = SourceText::new("abc def");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let mut actual = String::new();
+    CodeDisplay::new("lexer state")
+        .with_message_type(MessageType::Debug)
+        .with_color(false)
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "token")
+                .with_debug_type()))
+        .write(&mut actual, source)
+        .unwrap();
+
+    assert_eq!(actual, "\
+debug: lexer state
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc def
+  |     ... token
+");
+}
+
+
+/// Tests `SourceError::write_to` with a successful and a failing writer.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::source_error_write_to -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn source_error_write_to() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "source_error_write_to")
+        .entered();
+    let source = SourceText::new("abc def");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+    let error = SourceError::new(source, "write test")
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "here")));
+
+    let mut written = Vec::new();
+    error.write_to(&mut written, false).expect("successful write");
+    assert_eq!(String::from_utf8(written).unwrap(), format!("{error}"));
+
+    let mut buffer = [0u8; 8];
+    let failure = error
+        .write_to(&mut &mut buffer[..], false)
+        .unwrap_err();
+    assert_eq!(failure.kind(), std::io::ErrorKind::WriteZero);
+}
+
+
+/// Tests `SourceErrorOwned::from_utf8_error` for invalid UTF-8 input.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::source_error_from_utf8_error -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn source_error_from_utf8_error() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "source_error_from_utf8_error")
+        .entered();
+    let bytes = b"abc\xFFdef";
+
+    let error = SourceText::from_utf8(bytes).unwrap_err();
+    let actual = SourceErrorOwned::from_utf8_error(bytes, error);
+
+    assert_eq!(format!("{actual}"), "\
+error: invalid UTF-8
+ --> (0:0-0:7, bytes 0-9)
+  | 
+0 | abc\u{FFFD}def
+  |    ^ invalid UTF-8 at byte 3
+");
+
+    let source = SourceText::from_utf8(b"abc def").unwrap();
+    assert_eq!(source.as_str(), "abc def");
+}
+
+
+/// Tests `SourceError::with_secondary_span` widening the primary
+/// `SpanDisplay`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::source_error_secondary_span -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn source_error_secondary_span() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "source_error_secondary_span")
+        .entered();
+    let source = SourceText::new("[abc\ndef]");
+    let open = Span::enclosing(Pos::new(0, 0, 0), Pos::new(1, 0, 1));
+    let close = Span::enclosing(Pos::new(8, 1, 3), Pos::new(9, 1, 4));
+
+    let actual = SourceError::new(source, "mismatched brackets")
+        .with_primary_span(close, "closing bracket does not match")
+        .with_secondary_span(open, "... the open bracket here");
+
+    assert_eq!(format!("{actual}"), "\
+error: mismatched brackets
+ --> (0:0-1:4, bytes 0-9)
+  | 
+0 | [abc
+  | - ... the open bracket here
+1 | def]
+  |    ^ closing bracket does not match
+");
+}
+
+
+/// Tests a `Note` with its own `Highlight`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::note_with_highlight -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn note_with_highlight() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "note_with_highlight")
+        .entered();
+    let source = SourceText::new("abc abc");
+    let first = Span::enclosing(Pos::new(0, 0, 0), Pos::new(3, 0, 3));
+    let second = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let actual = SourceError::new(source, "redefinition")
+        .with_span_display(SpanDisplay::new_error_highlight(
+            source,
+            second,
+            "redefined here"))
+        .with_note(Note::new(MessageType::Note, "first defined here")
+            .with_highlight(Highlight::new(first, "first definition")
+                .with_note_type()));
+
+    assert_eq!(format!("{actual}"), "\
+error: redefinition
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc abc
+  |     ^^^ redefined here
+  = note: first defined here
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc abc
+  | --- first definition
+");
+}
+
+
+/// Tests collapsing consecutive identical `Note`s.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::note_collapse_identical -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn note_collapse_identical() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "note_collapse_identical")
+        .entered();
+    let source = SourceText::new("abc abc");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let actual = SourceError::new(source, "missing delimiter")
+        .with_span_display(SpanDisplay::new_error_highlight(
+            source,
+            span,
+            "expected ','"))
+        .with_note("consider adding a delimiter")
+        .with_note("consider adding a delimiter")
+        .with_note("consider adding a delimiter")
+        .with_note(Note::new(MessageType::Help, "consider adding a delimiter"))
+        .with_note("consider adding a delimiter");
+
+    assert_eq!(format!("{actual}"), "\
+error: missing delimiter
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc abc
+  |     ^^^ expected ','
+  = note: consider adding a delimiter (x 3)
+  = help: consider adding a delimiter
+  = note: consider adding a delimiter
+");
+}
+
+
+/// Tests `Highlight`s starting at the same column with different messages.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::highlight_aligned_messages -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn highlight_aligned_messages() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "highlight_aligned_messages")
+        .entered();
+    let source = SourceText::new("abc def");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+    let short = Span::enclosing(Pos::new(4, 0, 4), Pos::new(5, 0, 5));
+
+    let actual = SourceError::new(source, "aligned messages")
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "first message")
+                .with_error_type())
+            .with_highlight(Highlight::new(short, "second message")
+                .with_error_type()));
+
+    assert_eq!(format!("{actual}"), "\
+error: aligned messages
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc def
+  |     ^^^
+  |     |
+  |     first message
+  |     second message
+");
+}
+
+/// Tests identical `Highlight`s being merged.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::highlight_identical_merged -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn highlight_identical_merged() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "highlight_identical_merged")
+        .entered();
+    let source = SourceText::new("abc def");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let actual = SourceError::new(source, "identical messages")
+        .with_span_display(SpanDisplay::new(source, span)
+            .with_highlight(Highlight::new(span, "expected 'abc'")
+                .with_error_type())
+            .with_highlight(Highlight::new(span, "expected 'abc'")
+                .with_error_type()));
+
+    assert_eq!(format!("{actual}"), "\
+error: identical messages
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc def
+  |     ^^^ expected 'abc'
+");
+}
+
+
+/// Tests `SourceError` display with an error code.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::source_error_with_code -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn source_error_with_code() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "source_error_with_code")
+        .entered();
+    let source = SourceText::new("abc def");
+    let span = Span::enclosing(Pos::new(4, 0, 4), Pos::new(7, 0, 7));
+
+    let actual = SourceError::new(source, "invalid pattern")
+        .with_code("ABC-001")
+        .with_span_display(SpanDisplay::new_error_highlight(
+            source,
+            span,
+            "expected 'abc'"));
+
+    assert_eq!(actual.code(), Some("ABC-001"));
+    assert_eq!(format!("{actual}"), "\
+error[ABC-001]: invalid pattern
+ --> (0:0-0:7, bytes 0-7)
+  | 
+0 | abc def
+  |     ^^^ expected 'abc'
+");
+}
+
+
+/// Tests `Highlight` display of an empty span at the end of the text.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::abc_scanner::highlight_end_of_text -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn highlight_end_of_text() {
+    let _trace_guard = setup_test_environment();
+    let _trace_span = span!(Level::DEBUG, "highlight_end_of_text")
+        .entered();
+    let expected = "\
+error: unexpected end of text
+ --> (0:0-0:3, bytes 0-3)
+  | 
+0 | abc
+  |    \\ expected 'def'
+";
+
+    let source = SourceText::new("abc");
+    let span = Span::at(Pos::new(3, 0, 3));
+    let actual = SourceError::new(source, "unexpected end of text")
+        .with_span_display(SpanDisplay::new_error_highlight(
+            source,
+            span,
+            "expected 'def'"));
+    assert_eq!(format!("{actual}"), expected);
+
+    // The end of text following a line break is shown on the final line.
+    let source = SourceText::new("abc\n");
+    let span = Span::at(Pos::new(4, 1, 0));
+    let actual = SourceError::new(source, "unexpected end of text")
+        .with_span_display(SpanDisplay::new_error_highlight(
+            source,
+            span,
+            "expected 'def'"));
+    assert_eq!(format!("{actual}"), expected);
+}
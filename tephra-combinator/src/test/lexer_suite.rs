@@ -12,6 +12,7 @@
 // Internal library imports.
 use crate::both;
 use crate::one;
+use crate::one_kind;
 use crate::text;
 
 // External library imports.
@@ -23,6 +24,7 @@ use tephra::Pos;
 use tephra::Scanner;
 use tephra::SourceText;
 use tephra::SourceTextRef;
+use tephra::Span;
 
 // Standard library imports.
 use std::rc::Rc;
@@ -111,6 +113,63 @@ impl Scanner for Test {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Payload token parser.
+////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PayloadToken {
+    Word(String),
+    Num(u32),
+    Ws,
+}
+
+impl std::fmt::Display for PayloadToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use PayloadToken::*;
+        match self {
+            Word(word) => write!(f, "word '{word}'"),
+            Num(num)   => write!(f, "number {num}"),
+            Ws         => write!(f, "whitespace"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Payload;
+
+impl Scanner for Payload {
+    type Token = PayloadToken;
+
+    fn scan(&mut self, source: SourceTextRef<'_>, base: Pos)
+        -> Option<(Self::Token, Pos)>
+    {
+        let text = &source.as_ref()[base.byte..];
+        let metrics = source.column_metrics();
+
+        let (token, len) = if text.starts_with(char::is_alphabetic) {
+            let len = text
+                .find(|c: char| !c.is_alphabetic())
+                .unwrap_or(text.len());
+            (PayloadToken::Word(text[..len].to_owned()), len)
+
+        } else if text.starts_with(|c: char| c.is_ascii_digit()) {
+            let len = text
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(text.len());
+            (PayloadToken::Num(text[..len].parse().ok()?), len)
+
+        } else {
+            let rest = text.trim_start_matches(char::is_whitespace);
+            if rest.len() == text.len() { return None; }
+            (PayloadToken::Ws, text.len() - rest.len())
+        };
+
+        Some((
+            token,
+            metrics.end_position(&source.as_ref()[..base.byte + len], base)))
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Test setup
 ////////////////////////////////////////////////////////////////////////////////
@@ -217,6 +276,136 @@ fn simple_iter() {
 
 
 
+/// Tests `Lexer::span_tokens`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::lexer_suite::span_tokens -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn span_tokens() {
+    setup_test_environment();
+
+    use TestToken::*;
+    const TEXT: &str = "aa b def a";
+    let source = SourceText::new(TEXT);
+    let lexer = Lexer::new(Test::new(), source);
+
+    let span = Span::enclosing(Pos::new(3, 0, 3), Pos::new(7, 0, 7));
+    assert_eq!(
+        lexer
+            .span_tokens(Test::new(), span)
+            .map(|lex| (
+                lex.0,
+                format!("{:?} ({})", source.clipped(lex.1).as_str(), lex.1)))
+            .collect::<Vec<_>>(),
+        vec![
+            (B,  "\"b\" (0:3-0:4, bytes 3-4)".to_string()),
+            (Ws, "\" \" (0:4-0:5, bytes 4-5)".to_string()),
+        ]);
+}
+
+
+/// Tests `Lexer::scan_count`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::lexer_suite::scan_count -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn scan_count() {
+    setup_test_environment();
+
+    use TestToken::*;
+    const TEXT: &str = "aa b";
+    let source = SourceText::new(TEXT);
+    let mut lexer = Lexer::new(Test::new(), source)
+        .with_token_cache(true);
+
+    assert_eq!(lexer.scan_count(), 0);
+    assert_eq!(lexer.peek(), Some(Aa));
+    assert_eq!(lexer.next(), Some(Aa));
+    assert_eq!(lexer.scan_count(), 1);
+    assert_eq!(lexer.next(), Some(Ws));
+    assert_eq!(lexer.scan_count(), 2);
+
+    // Tokens scanned by a clone are reused.
+    let mut clone = lexer.clone();
+    assert_eq!(clone.next(), Some(B));
+    assert_eq!(lexer.next(), Some(B));
+    assert_eq!(lexer.scan_count(), 3);
+}
+
+
+/// Tests `Lexer::unget`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::lexer_suite::unget -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn unget() {
+    setup_test_environment();
+
+    use TestToken::*;
+    const TEXT: &str = "aa b";
+    let source = SourceText::new(TEXT);
+    let mut lexer = Lexer::new(Test::new(), source);
+    let _ = lexer.set_filter(Some(Rc::new(|tok| *tok != Ws)));
+
+    assert_eq!(lexer.next(), Some(Aa));
+    assert_eq!(lexer.next(), Some(B));
+    assert_eq!(lexer.token_span(),
+        Span::enclosing(Pos::new(3, 0, 3), Pos::new(4, 0, 4)));
+
+    lexer.unget();
+    assert_eq!(lexer.cursor_pos(), Pos::new(2, 0, 2));
+    assert_eq!(lexer.last_span(),
+        Some(Span::enclosing(Pos::new(0, 0, 0), Pos::new(2, 0, 2))));
+
+    // Only one token can be rewound.
+    lexer.unget();
+    assert_eq!(lexer.cursor_pos(), Pos::new(2, 0, 2));
+
+    assert_eq!(lexer.next(), Some(B));
+    assert_eq!(lexer.token_span(),
+        Span::enclosing(Pos::new(3, 0, 3), Pos::new(4, 0, 4)));
+    assert_eq!(lexer.next(), None);
+}
+
+
+/// Tests `Lexer::peek_nth`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::lexer_suite::peek_nth -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn peek_nth() {
+    setup_test_environment();
+
+    use TestToken::*;
+    const TEXT: &str = "aa b a";
+    let source = SourceText::new(TEXT);
+    let mut lexer = Lexer::new(Test::new(), source);
+    let _ = lexer.set_filter(Some(Rc::new(|tok| *tok != Ws)));
+    let expected = lexer
+        .clone()
+        .iter_with_spans()
+        .collect::<Vec<_>>();
+
+    assert_eq!(lexer.peek_nth(2), Some(A));
+    assert_eq!(lexer.peek_nth(0), Some(Aa));
+    assert_eq!(lexer.peek_nth(1), Some(B));
+    assert_eq!(lexer.peek_nth(3), None);
+    assert_eq!(lexer.cursor_pos(), Pos::new(0, 0, 0));
+    let scan_count = lexer.scan_count();
+
+    let actual = lexer
+        .iter_with_spans()
+        .collect::<Vec<_>>();
+
+    assert_eq!(actual, expected);
+    assert_eq!(lexer.scan_count(), scan_count);
+}
+
+
 /// Tests `Lexer` with whitespace filter.
 //
 // To collect trace output:
@@ -258,6 +447,83 @@ fn whitespace_filter() {
 }
 
 
+/// Tests `Lexer::push_filter`, `Lexer::pop_filter`, and
+/// `Lexer::with_filter_scope`.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::lexer_suite::filter_stack -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn filter_stack() {
+    setup_test_environment();
+
+    use TestToken::*;
+    const TEXT: &str = "aa b a b a";
+    let source = SourceText::new(TEXT);
+    let mut lexer = Lexer::new(Test::new(), source);
+    let _ = lexer.set_filter(Some(Rc::new(|tok| *tok != Ws)));
+
+    assert_eq!(lexer.next(), Some(Aa));
+
+    lexer.push_filter(Some(Rc::new(|_| true)));
+    assert_eq!(lexer.next(), Some(Ws));
+    assert!(lexer.pop_filter().is_some());
+    assert_eq!(lexer.next(), Some(B));
+
+    {
+        let mut scoped = lexer.with_filter_scope(Some(Rc::new(|_| true)));
+        assert_eq!(scoped.next(), Some(Ws));
+        assert_eq!(scoped.token_span(),
+            Span::enclosing(Pos::new(4, 0, 4), Pos::new(5, 0, 5)));
+        // Unpopped filters are discarded with the scope.
+        scoped.push_filter(None);
+    }
+
+    assert_eq!(lexer.next(), Some(A));
+    assert_eq!(lexer.next(), Some(B));
+    assert_eq!(lexer.token_span(),
+        Span::enclosing(Pos::new(7, 0, 7), Pos::new(8, 0, 8)));
+    assert!(lexer.pop_filter().is_none());
+    // The base filter is still applied.
+    assert_eq!(lexer.next(), Some(A));
+    assert_eq!(lexer.token_span(),
+        Span::enclosing(Pos::new(9, 0, 9), Pos::new(10, 0, 10)));
+}
+
+
+/// Tests that `Lexer::set_filter` discards tokens peeked under the previous
+/// filter.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::lexer_suite::set_filter_after_peek -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn set_filter_after_peek() {
+    setup_test_environment();
+
+    use TestToken::*;
+    const TEXT: &str = "aa b a";
+    let source = SourceText::new(TEXT);
+    let mut lexer = Lexer::new(Test::new(), source);
+
+    assert_eq!(lexer.next(), Some(Aa));
+    assert_eq!(lexer.peek(), Some(Ws));
+    assert_eq!(lexer.peek_nth(2), Some(Ws));
+
+    let _ = lexer.set_filter(Some(Rc::new(|tok| *tok != Ws)));
+    assert_eq!(lexer.peek(), Some(B));
+    assert_eq!(lexer.peek_nth(1), Some(A));
+    assert_eq!(lexer.next(), Some(B));
+    assert_eq!(lexer.token_span(),
+        Span::enclosing(Pos::new(3, 0, 3), Pos::new(4, 0, 4)));
+
+    lexer.push_filter(None);
+    assert_eq!(lexer.peek(), Some(Ws));
+    assert_eq!(lexer.next(), Some(Ws));
+    assert_eq!(lexer.next(), Some(A));
+}
+
+
 /// Tests `both` with whitespace filter.
 //
 // To collect trace output:
@@ -319,8 +585,10 @@ note: Lexer
  --> (0:0-0:5, bytes 0-5)
   | 
 0 | aa b 
-  | -- token (0:0-0:2, bytes 0-2)
-  | -- parse (0:0-0:2, bytes 0-2)
+  | --
+  | |
+  | token (0:0-0:2, bytes 0-2)
+  | parse (0:0-0:2, bytes 0-2)
   |   \\ cursor (0:2, byte 2), scanner: Test(Some(Aa))
 ");
 
@@ -416,8 +684,50 @@ note: Lexer
  --> (0:0-0:9, bytes 0-5)
   | 
 0 | 	aa	a
-  | ---- token (0:0-0:4, bytes 0-1)
-  | ---- parse (0:0-0:4, bytes 0-1)
+  | ----
+  | |
+  | token (0:0-0:4, bytes 0-1)
+  | parse (0:0-0:4, bytes 0-1)
   |     \\ cursor (0:4, byte 1), scanner: Test(Some(Ws))
 ");
 }
+
+
+/// Tests `one_kind` with payload-carrying tokens.
+//
+// To collect trace output:
+// RUST_LOG=TRACE cargo test --all-features test::lexer_suite::one_kind_payload -- --exact --nocapture > .trace
+#[test]
+#[timeout(50)]
+fn one_kind_payload() {
+    setup_test_environment();
+
+    use PayloadToken::*;
+    const TEXT: &str = "abc 42 def";
+    let source = SourceText::new(TEXT);
+    let lexer = Lexer::new(Payload, source)
+        .with_filter(Some(Rc::new(|tok| *tok != Ws)));
+    let ctx = Context::empty();
+
+    let (value, succ) = both(
+            one_kind(Word(String::new())),
+            one_kind(Num(0)))
+        (lexer, ctx.clone())
+        .expect("successful parse")
+        .take_value();
+
+    assert_eq!(value, (Word("abc".to_owned()), Num(42)));
+
+    let actual = one_kind(Num(0))
+        (succ.lexer, ctx)
+        .map_err(|e| e.into_source_error(source))
+        .unwrap_err();
+
+    assert_eq!(format!("{actual}"), "\
+error: unexpected token
+ --> (0:0-0:10, bytes 0-10)
+  | 
+0 | abc 42 def
+  |        ^^^ expected Num; found word 'def'
+");
+}
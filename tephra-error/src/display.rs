@@ -16,15 +16,19 @@ use crate::Note;
 
 // External library imports.
 use colored::Colorize as _;
+use tephra_span::CharWidth;
 use tephra_span::ColumnMetrics;
+use tephra_span::Pos;
 use tephra_span::SourceTextRef;
 use tephra_span::Span;
 use tephra_span::SplitLines;
 
 // Standard library imports.
 use std::borrow::Borrow as _;
+use std::borrow::Cow;
 use std::fmt::Display;
 use std::fmt::Write;
+use std::io;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -39,7 +43,7 @@ pub struct CodeDisplay {
     /// The overall message type for all of the spans.
     pub(in crate) message_type: MessageType,
     /// An error number or warning code to print.
-    pub(in crate) code_id: Option<&'static str>,
+    pub(in crate) code_id: Option<Cow<'static, str>>,
     /// The spans to display.
     pub(in crate) span_displays: Vec<SpanDisplay>,
     /// Notes to append after the displayed spans.
@@ -107,6 +111,13 @@ impl CodeDisplay {
         self
     }
 
+    /// Returns the given `CodeDisplay` with the debug `MessageType`.
+    #[must_use]
+    pub fn with_debug_type(mut self) -> Self {
+        self.message_type = MessageType::Debug;
+        self
+    }
+
     /// Returns the given `CodeDisplay` with the given `MessageType`.
     #[must_use]
     pub fn with_message_type(mut self, message_type: MessageType) -> Self {
@@ -117,7 +128,17 @@ impl CodeDisplay {
     /// Returns the given `CodeDisplay` with the given error code id.
     #[must_use]
     pub fn with_code_id(mut self, code_id: Option<&'static str>) -> Self {
-        self.code_id = code_id;
+        self.code_id = code_id.map(Cow::Borrowed);
+        self
+    }
+
+    /// Returns the given `CodeDisplay` with the given error code, which is
+    /// displayed in the header following the message type.
+    #[must_use]
+    pub fn with_code<C>(mut self, code: C) -> Self
+        where C: Into<Cow<'static, str>>,
+    {
+        self.code_id = Some(code.into());
         self
     }
 
@@ -162,6 +183,12 @@ impl CodeDisplay {
         self.message.as_str()
     }
 
+    /// Returns the `CodeDisplay`'s error code, if any.
+    #[must_use]
+    pub fn code(&self) -> Option<&str> {
+        self.code_id.as_deref()
+    }
+
     pub fn write<W>(
         &self,
         out: &mut W,
@@ -172,6 +199,27 @@ impl CodeDisplay {
         self.write_with_color_enablement(out, source, self.color_enabled)
     }
 
+    /// Writes the `CodeDisplay` directly to the given `io::Write` with the
+    /// given color enablement, without buffering the formatted output.
+    pub fn write_to(
+        &self,
+        w: &mut dyn io::Write,
+        source: SourceTextRef<'_>,
+        color_enabled: bool)
+        -> io::Result<()>
+    {
+        let mut out = IoWriteAdapter { inner: w, error: None };
+        match self.write_with_color_enablement(&mut out, source, color_enabled)
+        {
+            Ok(()) => Ok(()),
+            // Report the writer's error if it failed. Otherwise the error came
+            // from a `Display` implementation, and is wrapped as is.
+            Err(fmt_error) => Err(out.error
+                .take()
+                .unwrap_or_else(|| io::Error::other(fmt_error))),
+        }
+    }
+
     pub(in crate) fn write_with_color_enablement<W>(
         &self,
         out: &mut W,
@@ -182,8 +230,10 @@ impl CodeDisplay {
     {
         if color_enabled {
             write!(out, "{}", self.message_type)?;
-            if let Some(code_id) = self.code_id {
-                write!(out, "[{code_id}]")?;
+            if let Some(code_id) = &self.code_id {
+                write!(out, "{}", format!("[{code_id}]")
+                    .color(self.message_type.color())
+                    .bold())?;
             }
             writeln!(out, "{} {}",
                 ":".bright_white().bold(),
@@ -191,16 +241,20 @@ impl CodeDisplay {
         } else {
             self.message_type
                 .write_with_color_enablement(out, color_enabled)?;
+            if let Some(code_id) = &self.code_id {
+                write!(out, "[{code_id}]")?;
+            }
             writeln!(out, ": {}", self.message)?;
         }
         for span_display in &self.span_displays {
             span_display
                 .write_with_color_enablement(out, source, color_enabled)?;
         }
-        for note in &self.notes {
-            note.write_with_color_enablement(out, color_enabled)?;
-        }
-        Ok(())
+        // Align notes with the gutter of the last span display.
+        let gutter_width = self.span_displays
+            .last()
+            .map_or(0, |span_display| span_display.gutter_width);
+        write_notes(out, &self.notes, source, gutter_width, color_enabled)
     }
 }
 
@@ -225,25 +279,35 @@ pub struct SpanDisplay {
     pub(in crate) gutter_width: u8,
     /// TODO: Whether to allow line omissions within the source display.
     pub(in crate) _allow_omissions: bool,
+    /// The width to render tabs with, if tabs are to be expanded.
+    pub(in crate) render_tab_width: Option<usize>,
+    /// Suggested replacement text for spans of the source text.
+    pub(in crate) suggestions: Vec<(Span, String)>,
+    /// Whether to note highlighted lines indented with mixed tabs and spaces.
+    pub(in crate) lint_mixed_indent: bool,
 }
 
 impl SpanDisplay {
     /// Constructs a new `SpanDisplay` with the given span.
     #[must_use]
     pub fn new(source_text: SourceTextRef<'_>, span: Span) -> Self {
-        #[allow(clippy::cast_possible_truncation)]
-        #[allow(clippy::cast_sign_loss)]
-        let gutter_width = std::cmp::max(
-            (span.end().page.line as f32).log10().ceil() as u8, 1);
-
+        let span = display_span(source_text, span);
         Self {
-            source_name: source_text.name().map(String::from),
+            source_name: source_text.name().map(|name|
+                if source_text.is_synthetic() {
+                    format!("<{name}>")
+                } else {
+                    String::from(name)
+                }),
             _metrics: source_text.column_metrics(),
             span: span.widen_to_line(source_text),
             highlights: Vec::with_capacity(2),
             notes: Vec::new(),
             _allow_omissions: true,
-            gutter_width,
+            gutter_width: gutter_width_for_span(span),
+            render_tab_width: None,
+            suggestions: Vec::new(),
+            lint_mixed_indent: false,
         }
     }
 
@@ -277,6 +341,41 @@ impl SpanDisplay {
         self
     }
 
+    /// Attaches the given Highlight to the source span, widening the displayed
+    /// span to include the highlighted text.
+    pub fn push_highlight_widened(
+        &mut self,
+        source_text: SourceTextRef<'_>,
+        highlight: Highlight)
+    {
+        self.span = self.span.enclose(
+            display_span(source_text, highlight.span())
+                .widen_to_line(source_text));
+        self.gutter_width = gutter_width_for_span(self.span);
+        self.highlights.push(highlight);
+    }
+
+    /// Returns the given `SpanDisplay` with tabs in the source text rendered
+    /// as spaces up to the next multiple of the given width. Highlights are
+    /// aligned to the rendered tabs, independent of the tab width used to
+    /// measure their spans.
+    #[must_use]
+    pub fn with_render_tab_width(mut self, tab_width: usize) -> Self {
+        self.render_tab_width = Some(std::cmp::max(tab_width, 1));
+        self
+    }
+
+    /// Returns the given `SpanDisplay` with the mixed indentation lint
+    /// enabled or disabled. When enabled, a note is displayed if any
+    /// highlighted line is indented with both tabs and spaces, as its
+    /// highlights may appear misaligned for tab widths other than the one in
+    /// the source's `ColumnMetrics`.
+    #[must_use]
+    pub fn with_mixed_indent_lint(mut self, enabled: bool) -> Self {
+        self.lint_mixed_indent = enabled;
+        self
+    }
+
     /// Attaches the given Note to the source span.
     #[must_use]
     pub fn with_note(mut self, note: Note) -> Self {
@@ -284,6 +383,18 @@ impl SpanDisplay {
         self
     }
 
+    /// Attaches a suggestion to replace the given span with the given text.
+    /// The suggestion is displayed after the source span, showing the lines
+    /// containing the span before and after the replacement is applied.
+    #[must_use]
+    pub fn with_suggested_replacement<M>(mut self, span: Span, replacement: M)
+        -> Self
+        where M: Into<String>,
+    {
+        self.suggestions.push((span, replacement.into()));
+        self
+    }
+
     pub(in crate) fn write_with_color_enablement<W>(
         &self,
         out: &mut W,
@@ -315,20 +426,74 @@ impl SpanDisplay {
                 width=self.gutter_width as usize)?;
         }
 
+        let highlights = self.highlights
+            .iter()
+            .map(|hl| {
+                let mut hl = hl.clone();
+                hl.span = display_span(source_text, hl.span);
+                if let Some(tab_width) = self.render_tab_width {
+                    hl.span = Span::enclosing(
+                        render_tab_position(
+                            source_text,
+                            hl.span.start(),
+                            tab_width),
+                        render_tab_position(
+                            source_text,
+                            hl.span.end(),
+                            tab_width));
+                }
+                hl
+            })
+            .collect::<Vec<_>>();
+
         MultiSplitLines::new(
                 source_text,
                 self.span,
-                &self.highlights[..],
-                self.gutter_width)
+                &highlights[..],
+                self.gutter_width,
+                self.render_tab_width)
             .write_with_color_enablement(out, source_text, color_enabled)?;
 
-        for note in &self.notes {
+        for (span, replacement) in &self.suggestions {
+            write_suggestion(
+                out,
+                source_text,
+                *span,
+                replacement,
+                self.gutter_width,
+                color_enabled)?;
+        }
+
+        if self.lint_mixed_indent && self.has_mixed_indent(source_text) {
             write!(out, "{:width$} = ", "", width=self.gutter_width as usize)?;
-            note.write_with_color_enablement(out, color_enabled)?;
+            Note::new(MessageType::Note, "line uses mixed tabs and spaces")
+                .write_with_color_enablement(out, color_enabled)?;
             writeln!(out)?;
         }
 
-        Ok(())
+        write_notes(
+            out,
+            &self.notes,
+            source_text,
+            self.gutter_width,
+            color_enabled)
+    }
+
+    /// Returns true if any highlighted line of the displayed span is indented
+    /// with both tabs and spaces.
+    fn has_mixed_indent(&self, source_text: SourceTextRef<'_>) -> bool {
+        self.span
+            .split_lines(source_text)
+            .filter(|line| self.highlights
+                .iter()
+                .any(|hl| line.intersects(hl.span())))
+            .any(|line| {
+                let text = source_text.clipped(line);
+                let text = text.as_str();
+                let indent = &text[..text.len()
+                    - text.trim_start_matches([' ', '\t']).len()];
+                indent.contains(' ') && indent.contains('\t')
+            })
     }
 }
 
@@ -347,6 +512,8 @@ struct MultiSplitLines<'text, 'hl> {
     gutter_width: u8,
     /// TODO: The width of the highlight riser gutter.
     _riser_width: u8,
+    /// The width to render tabs with, if tabs are to be expanded.
+    render_tab_width: Option<usize>,
 }
 
 impl<'text, 'hl> MultiSplitLines<'text, 'hl>  {
@@ -356,7 +523,8 @@ impl<'text, 'hl> MultiSplitLines<'text, 'hl>  {
         source_text: SourceTextRef<'text>,
         span_display: Span,
         highlights: &'hl [Highlight],
-        gutter_width: u8)
+        gutter_width: u8,
+        render_tab_width: Option<usize>)
         -> Self
     {
         let _riser_width = highlights
@@ -373,6 +541,7 @@ impl<'text, 'hl> MultiSplitLines<'text, 'hl>  {
             highlights,
             gutter_width,
             _riser_width,
+            render_tab_width,
         }
     }
 
@@ -399,55 +568,423 @@ impl<'text, 'hl> MultiSplitLines<'text, 'hl>  {
                 });
         }
 
-        for span in self.source_lines {
+        for span in self.source_lines.clone() {
             let current_line = span.start().page.line;
+            let multiline_highlights_present = self.write_source_line(
+                out,
+                span,
+                source_text,
+                &mut riser_states[..],
+                color_enabled)?;
+            self.write_line_messages(
+                out,
+                current_line,
+                &mut riser_states[..],
+                multiline_highlights_present,
+                color_enabled)?;
+        }
+
+        self.write_messages_below(out, color_enabled)
+    }
 
-            // Write gutter for source line.
-            write_gutter(out, current_line, self.gutter_width, color_enabled)?;
+    /// Writes the gutter, risers, and source text for the given line. Returns
+    /// whether any multi-line highlights are present.
+    fn write_source_line<W>(
+        &self,
+        out: &mut W,
+        span: Span,
+        source_text: SourceTextRef<'text>,
+        riser_states: &mut [RiserState],
+        color_enabled: bool)
+        -> Result<bool, std::fmt::Error>
+        where W: Write
+    {
+        let current_line = span.start().page.line;
+
+        // Write gutter for source line.
+        write_gutter(out, current_line, self.gutter_width, color_enabled)?;
+
+        // Write risers for source line.
+        let mut multiline_highlights_present = false;
+        for (idx, hl) in self.highlights.iter().enumerate() {
+            hl.write_riser_for_line(
+                out,
+                current_line,
+                &mut riser_states[idx],
+                false,
+                color_enabled)?;
+            if hl.is_multiline() { multiline_highlights_present = true; }
+        }
+        if multiline_highlights_present { write!(out, " ")?; }
+
+        // Write source text.
+        let line_text = source_text.clipped(span);
+        match self.render_tab_width {
+            Some(tab_width) => write_tab_expanded(
+                out,
+                line_text.as_ref(),
+                tab_width,
+                source_text.column_metrics().char_width)?,
+            None => write!(out, "{}", line_text.as_ref())?,
+        }
+        writeln!(out)?;
+        Ok(multiline_highlights_present)
+    }
 
-            // Write risers for source line.
-            let mut multiline_highlights_present = false;
+    /// Writes the highlight messages for the given line.
+    fn write_line_messages<W>(
+        &self,
+        out: &mut W,
+        current_line: usize,
+        riser_states: &mut [RiserState],
+        multiline_highlights_present: bool,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        for (message_idx, message_hl) in self.highlights.iter().enumerate() {
+            if !message_hl.has_message_for_line(current_line) { continue; }
+
+            // Skip highlights already written with an earlier aligned
+            // highlight.
+            if self.highlights[..message_idx]
+                .iter()
+                .any(|hl| hl.is_aligned_with(message_hl, current_line))
+            {
+                continue;
+            }
+
+            // Collect the later highlights starting at the same column,
+            // merging any which are identical.
+            let mut aligned: Vec<&Highlight> = vec![message_hl];
+            for hl in &self.highlights[message_idx + 1..] {
+                if message_hl.is_aligned_with(hl, current_line)
+                    && !aligned.iter().any(|a| a.is_identical(hl))
+                {
+                    aligned.push(hl);
+                }
+            }
+            if aligned.len() > 1 {
+                self.write_aligned_messages(
+                    out,
+                    current_line,
+                    message_idx,
+                    &aligned[..],
+                    riser_states,
+                    multiline_highlights_present,
+                    color_enabled)?;
+                continue;
+            }
+
+            // Write message gutter.
+            write_gutter(out, "", self.gutter_width, color_enabled)?;
+            
             for (idx, hl) in self.highlights.iter().enumerate() {
+                // Write message risers.
                 hl.write_riser_for_line(
                     out,
                     current_line,
                     &mut riser_states[idx],
-                    false,
+                    message_idx == idx,
                     color_enabled)?;
-                if hl.is_multiline() { multiline_highlights_present = true; }
             }
-            if multiline_highlights_present { write!(out, " ")?; }
+            
+            // Write message.
+            message_hl.write_message_for_line(
+                out,
+                current_line,
+                multiline_highlights_present,
+                color_enabled)?;
 
-            // Write source text.
-            writeln!(out, "{}", source_text.clipped(span).as_ref())?;
-
-            for (message_idx, message_hl) in self.highlights.iter().enumerate()
-            {
-                if !message_hl.has_message_for_line(current_line) { continue; }
-
-                // Write message gutter.
+            // Write end message continuation.
+            if message_hl.has_continuation_for_line(current_line) {
                 write_gutter(out, "", self.gutter_width, color_enabled)?;
-                
                 for (idx, hl) in self.highlights.iter().enumerate() {
-                    // Write message risers.
-                    hl.write_riser_for_line(
+                    hl.write_continuation_riser(
                         out,
-                        current_line,
-                        &mut riser_states[idx],
-                        message_idx == idx,
+                        riser_states[idx],
                         color_enabled)?;
                 }
-                
-                // Write message.
-                message_hl.write_message_for_line(
+                message_hl.write_continuation_for_line(
                     out,
-                    current_line,
                     multiline_highlights_present,
                     color_enabled)?;
             }
         }
         Ok(())
     }
+
+    /// Writes the messages to be displayed below the highlighted lines.
+    fn write_messages_below<W>(&self, out: &mut W, color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        let riser_count = self.highlights
+            .iter()
+            .filter(|h| h.is_multiline())
+            .count();
+        for hl in self.highlights {
+            if !hl.has_message_below() { continue; }
+
+            write_gutter(out, "", self.gutter_width, color_enabled)?;
+            for _ in 0..=riser_count {
+                write!(out, " ")?;
+            }
+            hl.write_message_below(out, color_enabled)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the messages for a group of single-line highlights starting at
+    /// the same column. A shared underline is written, followed by a
+    /// connector line and each message on its own line.
+    #[allow(clippy::too_many_arguments)]
+    fn write_aligned_messages<W>(
+        &self,
+        out: &mut W,
+        current_line: usize,
+        message_idx: usize,
+        aligned: &[&Highlight],
+        riser_states: &mut [RiserState],
+        multiline_highlights_present: bool,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        let first = aligned[0];
+        let end_column = aligned
+            .iter()
+            .map(|hl| hl.span().end().page.column)
+            .max()
+            .unwrap_or(0);
+
+        // Write shared underline.
+        write_gutter(out, "", self.gutter_width, color_enabled)?;
+        for (idx, hl) in self.highlights.iter().enumerate() {
+            hl.write_riser_for_line(
+                out,
+                current_line,
+                &mut riser_states[idx],
+                message_idx == idx,
+                color_enabled)?;
+        }
+        first.write_aligned_underline(
+            out,
+            end_column,
+            multiline_highlights_present,
+            color_enabled)?;
+
+        // Write connector.
+        write_gutter(out, "", self.gutter_width, color_enabled)?;
+        for (idx, hl) in self.highlights.iter().enumerate() {
+            hl.write_continuation_riser(
+                out,
+                riser_states[idx],
+                color_enabled)?;
+        }
+        first.write_aligned_connector(
+            out,
+            multiline_highlights_present,
+            color_enabled)?;
+
+        // Write messages.
+        for hl in aligned {
+            write_gutter(out, "", self.gutter_width, color_enabled)?;
+            for (idx, riser_hl) in self.highlights.iter().enumerate() {
+                riser_hl.write_continuation_riser(
+                    out,
+                    riser_states[idx],
+                    color_enabled)?;
+            }
+            hl.write_aligned_message(
+                out,
+                multiline_highlights_present,
+                color_enabled)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the span at which the given span should be displayed. An empty span
+/// at the end of a text ending in a line break is displayed at the end of the
+/// final line.
+fn display_span(source_text: SourceTextRef<'_>, span: Span) -> Span {
+    if span.is_empty() {
+        Span::at(source_text.display_position(span.start()))
+    } else {
+        span
+    }
+}
+
+/// Returns the width of the line number gutter needed to display the given
+/// span.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn gutter_width_for_span(span: Span) -> u8 {
+    std::cmp::max((span.end().page.line as f32).log10().ceil() as u8, 1)
+}
+
+/// Returns the given position with its column measured with tabs rendered to
+/// the given width.
+fn render_tab_position(
+    source_text: SourceTextRef<'_>,
+    pos: Pos,
+    tab_width: usize)
+    -> Pos
+{
+    let metrics = source_text.column_metrics();
+    let line_break = metrics.line_ending.as_str();
+    let text = source_text.as_str();
+    let byte = pos.byte - source_text.start_position().byte;
+    let line_start = text[..byte]
+        .rfind(line_break)
+        .map_or(0, |idx| idx + line_break.len());
+
+    let mut column = 0;
+    for c in text[line_start..byte].chars() {
+        column += if c == '\t' {
+            tab_width - (column % tab_width)
+        } else {
+            metrics.char_width.width(c)
+        };
+    }
+    Pos::new(pos.byte, pos.page.line, column)
+}
+
+/// Writes the given notes aligned to the given gutter width. Consecutive
+/// identical notes are written once, followed by their repetition count.
+fn write_notes<W>(
+    out: &mut W,
+    notes: &[Note],
+    source_text: SourceTextRef<'_>,
+    gutter_width: u8,
+    color_enabled: bool)
+    -> std::fmt::Result
+    where W: Write
+{
+    let mut idx = 0;
+    while idx < notes.len() {
+        let note = &notes[idx];
+        let count = notes[idx..]
+            .iter()
+            .take_while(|other| other.is_identical(note))
+            .count();
+
+        write!(out, "{:width$} = ", "", width=gutter_width as usize)?;
+        note.write_with_color_enablement(out, color_enabled)?;
+        if count > 1 {
+            write!(out, " (x {count})")?;
+        }
+        writeln!(out)?;
+        note.write_highlight_with_color_enablement(
+            out,
+            source_text,
+            color_enabled)?;
+        idx += count;
+    }
+    Ok(())
+}
+
+/// Writes the given text with tabs rendered as spaces to the given width.
+fn write_tab_expanded<W>(
+    out: &mut W,
+    text: &str,
+    tab_width: usize,
+    char_width: CharWidth)
+    -> std::fmt::Result
+    where W: Write
+{
+    let mut column = 0;
+    for c in text.chars() {
+        if c == '\t' {
+            let spaces = tab_width - (column % tab_width);
+            write!(out, "{:spaces$}", "")?;
+            column += spaces;
+        } else {
+            out.write_char(c)?;
+            column += char_width.width(c);
+        }
+    }
+    Ok(())
+}
+
+/// Writes a suggestion to replace the given span with the given text. The
+/// lines containing the span are written marked with `-`, followed by the
+/// same lines with the replacement applied marked with `+`.
+fn write_suggestion<W>(
+    out: &mut W,
+    source_text: SourceTextRef<'_>,
+    span: Span,
+    replacement: &str,
+    gutter_width: u8,
+    color_enabled: bool)
+    -> std::fmt::Result
+    where W: Write
+{
+    let line_span = span.widen_to_line(source_text);
+    let line_break = source_text.column_metrics().line_ending.as_str();
+    let offset = source_text.start_position().byte;
+    let text = source_text.as_str();
+    let line_start = line_span.start().byte - offset;
+    let line_end = line_span.end().byte - offset;
+
+    let original = &text[line_start..line_end];
+    let replaced = format!("{}{}{}",
+        &text[line_start..span.start().byte - offset],
+        replacement,
+        &text[span.end().byte - offset..line_end]);
+
+    write!(out, "{:width$} = ", "", width=gutter_width as usize)?;
+    if color_enabled {
+        writeln!(out, "{}:", "suggestion"
+            .color(MessageType::Help.color())
+            .bold())?;
+    } else {
+        writeln!(out, "suggestion:")?;
+    }
+
+    let first_line = line_span.start().page.line;
+    for (idx, line) in original.split(line_break).enumerate() {
+        write_diff_line(out, first_line + idx, '-', line, gutter_width,
+            color_enabled)?;
+    }
+    for (idx, line) in replaced.split(line_break).enumerate() {
+        write_diff_line(out, first_line + idx, '+', line, gutter_width,
+            color_enabled)?;
+    }
+    Ok(())
+}
+
+/// Writes a line of a suggestion with the given line number and `-` or `+`
+/// marker.
+fn write_diff_line<W>(
+    out: &mut W,
+    line_number: usize,
+    marker: char,
+    line: &str,
+    gutter_width: u8,
+    color_enabled: bool)
+    -> std::fmt::Result
+    where W: Write
+{
+    if color_enabled {
+        let marker = if marker == '-' {
+            marker.to_string().bright_red().bold()
+        } else {
+            marker.to_string().bright_green().bold()
+        };
+        writeln!(out, "{:>width$} {} {}",
+            format!("{line_number}").bright_blue().bold(),
+            marker,
+            line,
+            width=gutter_width as usize)
+    } else {
+        writeln!(out, "{:>width$} {} {}",
+            line_number,
+            marker,
+            line,
+            width=gutter_width as usize)
+    }
 }
 
 fn write_gutter<V, W>(
@@ -476,3 +1013,25 @@ pub enum RiserState {
     Started,
     Ended,
 }
+
+
+////////////////////////////////////////////////////////////////////////////////
+// IoWriteAdapter
+////////////////////////////////////////////////////////////////////////////////
+/// Adapts an `io::Write` for use as a `fmt::Write`, retaining the first
+/// `io::Error` encountered.
+struct IoWriteAdapter<'w> {
+    inner: &'w mut dyn io::Write,
+    error: Option<io::Error>,
+}
+
+impl Write for IoWriteAdapter<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner
+            .write_all(s.as_bytes())
+            .map_err(|e| {
+                if self.error.is_none() { self.error = Some(e); }
+                std::fmt::Error
+            })
+    }
+}
@@ -10,15 +10,21 @@
 
 
 // Internal modules.
+mod committed;
 mod delimit;
 mod external;
 mod lexer;
+mod noted;
 mod source;
+mod warning;
 
 // Exports.
+pub use committed::*;
 pub use delimit::*;
 pub use lexer::*;
+pub use noted::*;
 pub use source::*;
+pub use warning::*;
 
 // External library imports.
 use tephra_span::SourceTextRef;
@@ -40,6 +46,10 @@ pub trait ParseError: std::error::Error + Send + Sync
     /// Returns `true` if the error type is recoverable.
     fn is_recoverable(&self) -> bool { true }
 
+    /// Returns `true` if the error occurred after the parse was committed to
+    /// an alternative, so no other alternatives should be attempted.
+    fn is_committed(&self) -> bool { false }
+
     /// Converts a `ParseError` into a `SourceErrorRef<'text>`.
     #[must_use]
     fn into_source_error(
@@ -51,6 +61,19 @@ pub trait ParseError: std::error::Error + Send + Sync
             .with_cause(self.into_error())
     }
 
+    /// Converts a `ParseError` into a Language Server Protocol `Diagnostic`,
+    /// located in the document with the given URI.
+    #[cfg(feature = "lsp")]
+    #[must_use]
+    fn into_lsp_diagnostic(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>,
+        uri: &lsp_types::Url)
+        -> lsp_types::Diagnostic
+    {
+        self.into_source_error(source_text).to_lsp_diagnostic(uri)
+    }
+
     /// Converts a `ParseError` into an owned error.
     fn into_error(self: Box<Self>)
         -> Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -0,0 +1,132 @@
+////////////////////////////////////////////////////////////////////////////////
+// Tephra parser library
+////////////////////////////////////////////////////////////////////////////////
+// Copyright 2022 Skylor R. Schermer
+// This code is dual licenced using the MIT or Apache 2 license.
+// See licence-mit.md and licence-apache.md for details.
+////////////////////////////////////////////////////////////////////////////////
+//! Committed parse errors.
+////////////////////////////////////////////////////////////////////////////////
+
+
+// Internal library imports.
+use crate::error::SourceErrorRef;
+use crate::ParseError;
+
+// External library imports.
+use tephra_span::SourceTextRef;
+use tephra_span::Span;
+
+// Standard library imports.
+use std::error::Error;
+use std::fmt::Display;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// CommittedError
+////////////////////////////////////////////////////////////////////////////////
+/// A `ParseError` which occurred after a parse was committed to an
+/// alternative. Alternative combinators return committed errors rather than
+/// attempting another alternative.
+#[derive(Debug)]
+pub struct CommittedError {
+    /// The wrapped error.
+    pub error: Box<dyn ParseError>,
+}
+
+impl CommittedError {
+    /// Constructs a new `CommittedError` wrapping the given error.
+    #[must_use]
+    pub fn new(error: Box<dyn ParseError>) -> Self {
+        Self { error }
+    }
+}
+
+impl Display for CommittedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl Error for CommittedError {}
+
+impl ParseError for CommittedError {
+    fn error_span(&self) -> Option<Span> {
+        self.error.error_span()
+    }
+
+    fn is_recoverable(&self) -> bool {
+        self.error.is_recoverable()
+    }
+
+    fn is_committed(&self) -> bool {
+        true
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        self.error.into_source_error(source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self.error.into_error()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// UnrecoverableError
+////////////////////////////////////////////////////////////////////////////////
+/// A `ParseError` which was committed by an `unrecoverable` parse with the
+/// `CommitPolicy::Abort` policy. Recovery combinators propagate unrecoverable
+/// errors rather than recovering from them.
+#[derive(Debug)]
+pub struct UnrecoverableError {
+    /// The wrapped error.
+    pub error: Box<dyn ParseError>,
+}
+
+impl UnrecoverableError {
+    /// Constructs a new `UnrecoverableError` wrapping the given error.
+    #[must_use]
+    pub fn new(error: Box<dyn ParseError>) -> Self {
+        Self { error }
+    }
+}
+
+impl Display for UnrecoverableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl Error for UnrecoverableError {}
+
+impl ParseError for UnrecoverableError {
+    fn error_span(&self) -> Option<Span> {
+        self.error.error_span()
+    }
+
+    fn is_recoverable(&self) -> bool {
+        false
+    }
+
+    fn is_committed(&self) -> bool {
+        self.error.is_committed()
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        self.error.into_source_error(source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self.error.into_error()
+    }
+}
@@ -13,6 +13,8 @@
 use crate::error::SourceError;
 use crate::error::SourceErrorRef;
 use crate::Highlight;
+use crate::MessageType;
+use crate::Note;
 use crate::ParseError;
 use crate::SpanDisplay;
 
@@ -107,26 +109,54 @@ impl ParseError for ParseBoundaryError {
 ////////////////////////////////////////////////////////////////////////////////
 // MatchBracketError
 ////////////////////////////////////////////////////////////////////////////////
-/// An error generated when a successful parse does not consume as much text as
-/// required.
-#[derive(Debug, Clone, Copy)]
+/// An error generated when brackets cannot be matched.
+///
+/// Bracket combinators return this error directly, so the span of the
+/// offending bracket can be recovered by downcasting the failure, or by using
+/// [`MatchBracketError::find_in`] once the error has been converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MatchBracketError {
+    /// No open bracket was found.
     NoneFound {
+        /// The span where an open bracket was expected.
         expected_start: Span,
     },
+    /// An open bracket was found without a matching close bracket.
     Unclosed {
+        /// The span of the unmatched open bracket.
         found_start: Span,
     },
+    /// A close bracket was found without a matching open bracket.
     Unopened {
+        /// The span of the unmatched close bracket.
         found_end: Span,
     },
+    /// An open bracket was closed by a bracket of a different kind.
     Mismatch {
+        /// The span of the open bracket.
         found_start: Span,
+        /// The span of the mismatched close bracket.
         found_end: Span,
     },
 }
 
 impl MatchBracketError {
+    /// Returns the first `MatchBracketError` in the given error or its chain of
+    /// sources. This allows the spans of the offending brackets to be found
+    /// after the error has been converted, such as in the `SourceError`s
+    /// collected by the `Context` during error recovery.
+    #[must_use]
+    pub fn find_in<'e>(error: &'e (dyn Error + 'static)) -> Option<&'e Self> {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(bracket_error) = error.downcast_ref::<Self>() {
+                return Some(bracket_error);
+            }
+            current = error.source();
+        }
+        None
+    }
+
     /// Returns the full span of the error.
     fn full_span(&self) -> Span {
         use MatchBracketError::*;
@@ -313,3 +343,428 @@ impl ParseError for RepeatCountError {
         self
     }
 }
+
+
+////////////////////////////////////////////////////////////////////////////////
+// EmptyListError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated when a list requiring at least one item is empty.
+#[derive(Debug, Clone, Copy)]
+pub struct EmptyListError {
+    /// The span of the empty list.
+    pub error_span: Span,
+}
+
+impl EmptyListError {
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    #[must_use]
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        SourceError::new(source_text, "empty list")
+            .with_span_display(SpanDisplay::new_error_highlight(
+                source_text,
+                self.error_span,
+                "expected at least one item"))
+            .with_cause(Box::new(self))
+    }
+}
+
+impl Display for EmptyListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "empty list: expected at least one item at {}",
+            self.error_span)
+    }
+}
+
+impl Error for EmptyListError {}
+
+impl ParseError for EmptyListError {
+    fn error_span(&self) -> Option<Span> {
+        Some(self.error_span)
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// UnexpectedSeparatorError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated when a list begins or ends with a separator which is not
+/// permitted.
+#[derive(Debug, Clone, Copy)]
+pub struct UnexpectedSeparatorError {
+    /// The span of the separator.
+    pub error_span: Span,
+    /// Whether the separator is trailing the list. Otherwise it is leading.
+    pub trailing: bool,
+}
+
+impl UnexpectedSeparatorError {
+    /// Returns the description of the separator position.
+    fn description(&self) -> &'static str {
+        if self.trailing {
+            "unexpected trailing separator"
+        } else {
+            "unexpected leading separator"
+        }
+    }
+
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    #[must_use]
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        SourceError::new(source_text, self.description())
+            .with_span_display(SpanDisplay::new_error_highlight(
+                source_text,
+                self.error_span,
+                "separator not permitted here"))
+            .with_cause(Box::new(self))
+    }
+}
+
+impl Display for UnexpectedSeparatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.description(), self.error_span)
+    }
+}
+
+impl Error for UnexpectedSeparatorError {}
+
+impl ParseError for UnexpectedSeparatorError {
+    fn error_span(&self) -> Option<Span> {
+        Some(self.error_span)
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// NonAdvancingRepetitionError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated when a repeated parse succeeds without consuming any
+/// text, which would otherwise repeat forever.
+#[derive(Debug, Clone, Copy)]
+pub struct NonAdvancingRepetitionError {
+    /// The span of the repetition's position.
+    pub error_span: Span,
+}
+
+impl NonAdvancingRepetitionError {
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    #[must_use]
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        SourceError::new(source_text, "non-advancing repetition")
+            .with_span_display(SpanDisplay::new_error_highlight(
+                source_text,
+                self.error_span,
+                "repeated parse consumed no text here"))
+            .with_cause(Box::new(self))
+    }
+}
+
+impl Display for NonAdvancingRepetitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "non-advancing repetition at {}", self.error_span)
+    }
+}
+
+impl Error for NonAdvancingRepetitionError {}
+
+impl ParseError for NonAdvancingRepetitionError {
+    fn error_span(&self) -> Option<Span> {
+        Some(self.error_span)
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// AmbiguousParseError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated when more than one alternative parse succeeds.
+#[derive(Debug, Clone)]
+pub struct AmbiguousParseError {
+    /// The span of the ambiguous text.
+    pub error_span: Span,
+    /// The index and matched span of each successful alternative.
+    pub matches: Vec<(usize, Span)>,
+}
+
+impl AmbiguousParseError {
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    #[must_use]
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        let mut span_display = SpanDisplay::new_error_highlight(
+            source_text,
+            self.error_span,
+            "input matches multiple interpretations");
+        for (index, span) in &self.matches {
+            span_display = span_display.with_note(Note::new(
+                MessageType::Note,
+                format!("interpretation {index} matched ({span})")));
+        }
+
+        SourceError::new(source_text, "ambiguous parse")
+            .with_span_display(span_display)
+            .with_cause(Box::new(self))
+    }
+}
+
+impl Display for AmbiguousParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ambiguous parse: {} interpretations matched at {}",
+            self.matches.len(),
+            self.error_span)
+    }
+}
+
+impl Error for AmbiguousParseError {}
+
+impl ParseError for AmbiguousParseError {
+    fn error_span(&self) -> Option<Span> {
+        Some(self.error_span)
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ChoiceError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated when none of a set of alternative parses succeeds.
+#[derive(Debug)]
+pub struct ChoiceError {
+    /// The span of the attempted alternatives.
+    pub error_span: Span,
+    /// The failure of each alternative, in the order attempted.
+    pub failures: Vec<Box<dyn ParseError>>,
+}
+
+impl ChoiceError {
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    #[must_use]
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        let mut span_display = SpanDisplay::new_error_highlight(
+            source_text,
+            self.error_span,
+            "no alternative matched");
+        for (index, failure) in self.failures.iter().enumerate() {
+            span_display = span_display.with_note(Note::new(
+                MessageType::Note,
+                format!("alternative {index} failed: {failure}")));
+        }
+
+        SourceError::new(source_text, format!(
+                "expected one of {} alternatives",
+                self.failures.len()))
+            .with_span_display(span_display)
+            .with_cause(Box::new(self))
+    }
+}
+
+impl Display for ChoiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected one of {} alternatives at {}",
+            self.failures.len(),
+            self.error_span)
+    }
+}
+
+impl Error for ChoiceError {}
+
+impl ParseError for ChoiceError {
+    fn error_span(&self) -> Option<Span> {
+        Some(self.error_span)
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// FailureError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated unconditionally by a parser, with a given message.
+#[derive(Debug, Clone)]
+pub struct FailureError {
+    /// The span of the failure.
+    pub error_span: Span,
+    /// The error message.
+    pub message: String,
+}
+
+impl FailureError {
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    #[must_use]
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        SourceError::new(source_text, self.message.clone())
+            .with_span_display(SpanDisplay::new_error_highlight(
+                source_text,
+                self.error_span,
+                self.message.clone()))
+            .with_cause(Box::new(self))
+    }
+}
+
+impl Display for FailureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.message, self.error_span)
+    }
+}
+
+impl Error for FailureError {}
+
+impl ParseError for FailureError {
+    fn error_span(&self) -> Option<Span> {
+        Some(self.error_span)
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ErrorLimitError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated when the maximum number of errors for a parse is
+/// exceeded. This error is not recoverable, so it aborts the parse.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorLimitError {
+    /// The span of the error which exceeded the limit, if available.
+    pub error_span: Option<Span>,
+    /// The maximum number of errors for the parse.
+    pub limit: usize,
+}
+
+impl ErrorLimitError {
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    #[must_use]
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        let mut source_error = SourceError::new(
+                source_text,
+                "too many errors; aborting")
+            .with_note(format!(
+                "the parse is limited to {} errors",
+                self.limit));
+        if let Some(span) = self.error_span {
+            source_error.push_span_display(SpanDisplay::new_error_highlight(
+                source_text,
+                span,
+                "error limit exceeded here"));
+        }
+        source_error.with_cause(Box::new(self))
+    }
+}
+
+impl Display for ErrorLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "too many errors; aborting after {} errors", self.limit)
+    }
+}
+
+impl Error for ErrorLimitError {}
+
+impl ParseError for ErrorLimitError {
+    fn error_span(&self) -> Option<Span> {
+        self.error_span
+    }
+
+    fn is_recoverable(&self) -> bool {
+        false
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
@@ -256,6 +256,73 @@ impl<T> ParseError for UnexpectedTokenError<T>
 }
 
 
+////////////////////////////////////////////////////////////////////////////////
+// MissingTokenError
+////////////////////////////////////////////////////////////////////////////////
+/// An error generated when an expected token is missing and a synthetic token
+/// is inserted in its place.
+#[derive(Debug, Clone)]
+pub struct MissingTokenError<T>
+    where T: Debug + Display + Send + Sync + 'static
+{
+    /// The empty span where the token was inserted.
+    pub error_span: Span,
+    /// The missing token.
+    pub expected: T,
+    /// The error message.
+    pub message: String,
+}
+
+impl<T> MissingTokenError<T> where T: Debug + Display + Send + Sync {
+    /// Converts the error into a `SourceError` attached to the given
+    /// `SourceText`.
+    pub fn into_source_error(self, source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        SourceError::new(source_text, self.message.clone())
+            .with_span_display(SpanDisplay::new_error_highlight(
+                source_text,
+                self.error_span,
+                format!("expected {}", self.expected)))
+            .with_cause(Box::new(self))
+    }
+}
+
+impl<T> Display for MissingTokenError<T>
+    where T: Debug + Display + Send + Sync + 'static
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: expected {} at {}",
+            self.message,
+            self.expected,
+            self.error_span)
+    }
+}
+
+impl<T> Error for MissingTokenError<T>
+    where T: Debug + Display + Send + Sync + 'static {}
+
+impl<T> ParseError for MissingTokenError<T>
+    where T: Debug + Display + Send + Sync + 'static
+{
+    fn error_span(&self) -> Option<Span> {
+        Some(self.error_span)
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        Self::into_source_error(*self, source_text)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+}
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // RecoverError
 ////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,91 @@
+////////////////////////////////////////////////////////////////////////////////
+// Tephra parser library
+////////////////////////////////////////////////////////////////////////////////
+// Copyright 2022 Skylor R. Schermer
+// This code is dual licenced using the MIT or Apache 2 license.
+// See licence-mit.md and licence-apache.md for details.
+////////////////////////////////////////////////////////////////////////////////
+//! Parse errors with attached notes.
+////////////////////////////////////////////////////////////////////////////////
+
+
+// Internal library imports.
+use crate::error::SourceErrorRef;
+use crate::Note;
+use crate::ParseError;
+
+// External library imports.
+use tephra_span::SourceTextRef;
+use tephra_span::Span;
+
+// Standard library imports.
+use std::error::Error;
+use std::fmt::Display;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// NotedError
+////////////////////////////////////////////////////////////////////////////////
+/// A `ParseError` wrapped with additional notes, which are appended to the
+/// error's notes when it is converted into a `SourceError`.
+#[derive(Debug)]
+pub struct NotedError {
+    /// The wrapped error.
+    pub error: Box<dyn ParseError>,
+    /// The notes to attach to the error.
+    pub notes: Vec<Note>,
+}
+
+impl NotedError {
+    /// Constructs a new `NotedError` wrapping the given error with the given
+    /// note.
+    #[must_use]
+    pub fn new(error: Box<dyn ParseError>, note: Note) -> Self {
+        Self {
+            error,
+            notes: vec![note],
+        }
+    }
+}
+
+impl Display for NotedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)?;
+        for note in &self.notes {
+            write!(f, "; {}", note.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for NotedError {}
+
+impl ParseError for NotedError {
+    fn error_span(&self) -> Option<Span> {
+        self.error.error_span()
+    }
+
+    fn is_recoverable(&self) -> bool {
+        self.error.is_recoverable()
+    }
+
+    fn is_committed(&self) -> bool {
+        self.error.is_committed()
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        let mut source_error = self.error.into_source_error(source_text);
+        for note in self.notes {
+            source_error.push_note(note);
+        }
+        source_error
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self.error.into_error()
+    }
+}
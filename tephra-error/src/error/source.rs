@@ -11,18 +11,27 @@
 
 // Internal library imports.
 use crate::CodeDisplay;
+use crate::Highlight;
+use crate::MessageType;
 use crate::Note;
 use crate::SpanDisplay;
 
 
 // External library imports.
+#[cfg(feature = "lsp")]
+use tephra_span::CharWidth;
+use tephra_span::Pos;
 use tephra_span::SourceText;
+use tephra_span::Span;
 
 
 // Standard library imports.
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::io;
+use std::str::Utf8Error;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -68,6 +77,112 @@ impl<T> SourceError<T> where T: AsRef<str> {
         self
     }
 
+    /// Returns the given `SourceError` with the given error code. The code is
+    /// displayed in the error header, as in `error[E001]: message`.
+    #[must_use]
+    pub fn with_code<C>(mut self, code: C) -> Self
+        where C: Into<Cow<'static, str>>,
+    {
+        self.code_display.code_id = Some(code.into());
+        self
+    }
+
+    /// Returns a JSON representation of the error, containing its message,
+    /// message type, error code, source name, and highlights.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let highlights: Vec<_> = self.code_display.span_displays
+            .iter()
+            .flat_map(|span_display| span_display.highlights.iter())
+            .map(Highlight::to_json)
+            .collect();
+
+        serde_json::json!({
+            "message": self.code_display.message,
+            "message_type": self.code_display.message_type.name(),
+            "code": self.code_display.code(),
+            "source_name": self.source_text.name(),
+            "highlights": highlights,
+        })
+    }
+
+    /// Returns a Language Server Protocol `Diagnostic` for the error, located
+    /// in the document with the given URI.
+    ///
+    /// The range of the diagnostic is taken from the first highlight, and its
+    /// related information from any further highlights. Columns are measured
+    /// in UTF-16 code units, as required by the protocol.
+    #[cfg(feature = "lsp")]
+    #[must_use]
+    pub fn to_lsp_diagnostic(&self, uri: &lsp_types::Url)
+        -> lsp_types::Diagnostic
+    {
+        use lsp_types::Diagnostic;
+        use lsp_types::DiagnosticRelatedInformation;
+        use lsp_types::Location;
+        use lsp_types::NumberOrString;
+
+        let mut highlights = self.code_display.span_displays
+            .iter()
+            .flat_map(|span_display| span_display.highlights.iter());
+
+        let range = highlights
+            .next()
+            .map(|hl| self.lsp_range(hl.span()))
+            .unwrap_or_default();
+
+        let related: Vec<_> = highlights
+            .map(|hl| DiagnosticRelatedInformation {
+                location: Location {
+                    uri: uri.clone(),
+                    range: self.lsp_range(hl.span()),
+                },
+                message: hl.message().unwrap_or_default().to_owned(),
+            })
+            .collect();
+
+        Diagnostic {
+            range,
+            severity: Some(self.code_display.message_type.lsp_severity()),
+            code: self.code_display
+                .code()
+                .map(|code| NumberOrString::String(code.to_owned())),
+            source: self.source_text.name().map(String::from),
+            message: self.code_display.message.clone(),
+            related_information: (!related.is_empty()).then_some(related),
+            ..Diagnostic::default()
+        }
+    }
+
+    /// Returns the Language Server Protocol `Range` of the given span.
+    #[cfg(feature = "lsp")]
+    fn lsp_range(&self, span: Span) -> lsp_types::Range {
+        lsp_types::Range {
+            start: self.lsp_position(span.start()),
+            end: self.lsp_position(span.end()),
+        }
+    }
+
+    /// Returns the Language Server Protocol `Position` of the given position,
+    /// with its column measured in UTF-16 code units.
+    #[cfg(feature = "lsp")]
+    fn lsp_position(&self, pos: Pos) -> lsp_types::Position {
+        let source_text = self.source_text.borrow();
+        let offset = source_text.start_position().byte;
+        let line_start = source_text.line_start_position(pos).byte;
+        let character: usize = source_text
+            .as_str()[line_start - offset..pos.byte - offset]
+            .chars()
+            .map(|c| CharWidth::Utf16.width(c))
+            .sum();
+
+        lsp_types::Position {
+            line: u32::try_from(pos.page.line).unwrap_or(u32::MAX),
+            character: u32::try_from(character).unwrap_or(u32::MAX),
+        }
+    }
+
     /// Returns the given `SourceError` with the given color enablement.
     #[must_use]
     pub fn with_color(mut self, color_enabled: bool) -> Self {
@@ -75,6 +190,14 @@ impl<T> SourceError<T> where T: AsRef<str> {
         self
     }
 
+    /// Returns the given `SourceError` with the given `MessageType`.
+    #[must_use]
+    pub fn with_message_type(mut self, message_type: MessageType) -> Self {
+        self.code_display = self.code_display
+            .with_message_type(message_type);
+        self
+    }
+
     /// Returns the given `SourceError` with the given note attachment.
     #[must_use]
     pub fn with_note<N>(mut self, note: N) -> Self
@@ -100,6 +223,40 @@ impl<T> SourceError<T> where T: AsRef<str> {
         self
     }
 
+    /// Returns the given `SourceError` with the given primary span. The primary
+    /// span is highlighted as an error, and its `SpanDisplay` is placed first,
+    /// providing the location shown in the error header.
+    #[must_use]
+    pub fn with_primary_span<M>(mut self, span: Span, message: M) -> Self
+        where M: Into<String>,
+    {
+        let span_display = SpanDisplay::new_error_highlight(
+            self.source_text.borrow(),
+            span,
+            message);
+        self.code_display.span_displays.insert(0, span_display);
+        self
+    }
+
+    /// Returns the given `SourceError` with the given secondary span. The
+    /// secondary span is highlighted as a note within the primary
+    /// `SpanDisplay`, or within a new `SpanDisplay` if none is present.
+    #[must_use]
+    pub fn with_secondary_span<M>(mut self, span: Span, message: M) -> Self
+        where M: Into<String>,
+    {
+        let source_text = self.source_text.borrow();
+        let highlight = Highlight::new(span, message).with_note_type();
+        match self.code_display.span_displays.first_mut() {
+            Some(span_display) => span_display
+                .push_highlight_widened(source_text, highlight),
+            None => self.code_display.span_displays
+                .push(SpanDisplay::new(source_text, span)
+                    .with_highlight(highlight)),
+        }
+        self
+    }
+
     /// Appends the given `SpanDisplay` to the `SourceError`.
     pub fn push_span_display<S>(&mut self, span_display: S)
         where S: Into<SpanDisplay>
@@ -107,11 +264,25 @@ impl<T> SourceError<T> where T: AsRef<str> {
         self.code_display.span_displays.push(span_display.into());
     }
 
+    /// Writes the `SourceError` directly to the given `io::Write` with the
+    /// given color enablement, without buffering the formatted output.
+    pub fn write_to(&self, w: &mut dyn io::Write, color_enabled: bool)
+        -> io::Result<()>
+    {
+        self.code_display
+            .write_to(w, self.source_text.borrow(), color_enabled)
+    }
+
     /// Returns the `SourceError`'s message.
     pub fn message(&self) -> &str {
         self.code_display.message.as_str()
     }
 
+    /// Returns the `SourceError`'s error code, if any.
+    pub fn code(&self) -> Option<&str> {
+        self.code_display.code()
+    }
+
 
     #[must_use]
     pub fn into_owned(self) -> SourceErrorOwned {
@@ -123,6 +294,34 @@ impl<T> SourceError<T> where T: AsRef<str> {
     }
 }
 
+impl SourceErrorOwned {
+    /// Constructs a new `SourceErrorOwned` highlighting the first invalid
+    /// UTF-8 sequence in the given bytes. The bytes are displayed with invalid
+    /// sequences replaced by `U+FFFD REPLACEMENT CHARACTER`.
+    #[must_use]
+    pub fn from_utf8_error(bytes: &[u8], error: Utf8Error) -> Self {
+        let text = String::from_utf8_lossy(bytes);
+        let source_text = SourceText::new(text.as_ref());
+        let valid = error.valid_up_to();
+
+        // The text preceding the first invalid sequence is unchanged by the
+        // lossy conversion, so the replacement character starts at the same
+        // byte.
+        let start = source_text
+            .column_metrics()
+            .end_position(&text[..valid], Pos::ZERO);
+        let end = source_text.next_position(start).unwrap_or(start);
+
+        SourceError::new(source_text, "invalid UTF-8")
+            .with_span_display(SpanDisplay::new_error_highlight(
+                source_text,
+                Span::enclosing(start, end),
+                format!("invalid UTF-8 at byte {valid}")))
+            .with_cause(Box::new(error))
+            .into_owned()
+    }
+}
+
 impl<T> Display for SourceError<T> where T: AsRef<str> + Display {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.code_display.write(f, self.source_text.borrow())
@@ -0,0 +1,71 @@
+////////////////////////////////////////////////////////////////////////////////
+// Tephra parser library
+////////////////////////////////////////////////////////////////////////////////
+// Copyright 2022 Skylor R. Schermer
+// This code is dual licenced using the MIT or Apache 2 license.
+// See licence-mit.md and licence-apache.md for details.
+////////////////////////////////////////////////////////////////////////////////
+//! Parse warnings.
+////////////////////////////////////////////////////////////////////////////////
+
+
+// Internal library imports.
+use crate::error::SourceErrorRef;
+use crate::MessageType;
+use crate::ParseError;
+
+// External library imports.
+use tephra_span::SourceTextRef;
+use tephra_span::Span;
+
+// Standard library imports.
+use std::error::Error;
+use std::fmt::Display;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// WarningError
+////////////////////////////////////////////////////////////////////////////////
+/// A `ParseError` reported as a warning. The wrapped error is displayed with
+/// the warning `MessageType` when it is converted into a `SourceError`.
+#[derive(Debug)]
+pub struct WarningError {
+    /// The wrapped error.
+    pub error: Box<dyn ParseError>,
+}
+
+impl WarningError {
+    /// Constructs a new `WarningError` wrapping the given error.
+    #[must_use]
+    pub fn new(error: Box<dyn ParseError>) -> Self {
+        Self { error }
+    }
+}
+
+impl Display for WarningError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "warning: {}", self.error)
+    }
+}
+
+impl Error for WarningError {}
+
+impl ParseError for WarningError {
+    fn error_span(&self) -> Option<Span> {
+        self.error.error_span()
+    }
+
+    fn into_source_error(
+        self: Box<Self>,
+        source_text: SourceTextRef<'_>)
+        -> SourceErrorRef<'_>
+    {
+        self.error
+            .into_source_error(source_text)
+            .with_message_type(MessageType::Warning)
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self.error.into_error()
+    }
+}
@@ -28,13 +28,20 @@ use std::fmt::Write;
 #[derive(Debug, Clone)]
 pub struct Highlight {
     /// The span to highlight.
-    span: Span,
+    pub(in crate) span: Span,
     /// The message to display at the start of the span.
     start_message: Option<String>,
     /// The message to display at the end of the span.
     end_message: Option<String>,
     /// The message type.
     message_type: MessageType,
+    /// Whether to display the end message below the highlighted lines.
+    message_below: bool,
+    /// The glyph used to mark an empty span's insertion point.
+    insertion_glyph: char,
+    /// The glyph used to underline the span, overriding the message type's
+    /// underline.
+    underline_glyph: Option<char>,
     // TODO: Whether to allow line omissions within the highlighted span.
     _allow_omissions: bool,
 }
@@ -50,6 +57,9 @@ impl Highlight {
             start_message: None,
             end_message: Some(message.into()),
             message_type: MessageType::Info,
+            message_below: false,
+            insertion_glyph: '\\',
+            underline_glyph: None,
             _allow_omissions: true,
         }
     }
@@ -89,6 +99,13 @@ impl Highlight {
         self
     }
 
+    /// Returns the given `CodeDisplay` with the debug `MessageType`.
+    #[must_use]
+    pub fn with_debug_type(mut self) -> Self {
+        self.message_type = MessageType::Debug;
+        self
+    }
+
     /// Returns the given `CodeDisplay` with the given `MessageType`.
     #[must_use]
     pub fn with_message_type(mut self, message_type: MessageType) -> Self {
@@ -96,6 +113,77 @@ impl Highlight {
         self
     }
 
+    /// Returns the given `Highlight` with the given message displayed at the
+    /// start of the span.
+    #[must_use]
+    pub fn with_start_message<M>(mut self, message: M) -> Self
+        where M: Into<String>,
+    {
+        self.start_message = Some(message.into());
+        self
+    }
+
+    /// Returns the given `Highlight` with its end message displayed on a
+    /// separate line below the highlighted lines, rather than at the end of the
+    /// span. This only affects multi-line highlights.
+    #[must_use]
+    pub fn with_message_below(mut self) -> Self {
+        self.message_below = true;
+        self
+    }
+
+    /// Returns the given `Highlight` with the given glyph used to mark the
+    /// insertion point of an empty span. The glyph is placed beneath the
+    /// character immediately following the insertion point.
+    #[must_use]
+    pub fn with_insertion_glyph(mut self, glyph: char) -> Self {
+        self.insertion_glyph = glyph;
+        self
+    }
+
+    /// Returns the given `Highlight` with the given glyph used to underline
+    /// the span, in place of the underline for its message type.
+    #[must_use]
+    pub fn with_underline_char(mut self, glyph: char) -> Self {
+        self.underline_glyph = Some(glyph);
+        self
+    }
+
+    /// Returns the highlighted span.
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Returns the highlight's message, preferring the end message if both
+    /// start and end messages are present.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.end_message
+            .as_deref()
+            .or(self.start_message.as_deref())
+    }
+
+    /// Returns a JSON representation of the highlight.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let start = self.span.start();
+        let end = self.span.end();
+        serde_json::json!({
+            "start_byte": start.byte,
+            "end_byte": end.byte,
+            "start_line": start.page.line,
+            "start_col": start.page.column,
+            "end_line": end.page.line,
+            "end_col": end.page.column,
+            "message": self.end_message
+                .as_ref()
+                .or(self.start_message.as_ref()),
+            "severity": self.message_type.name(),
+        })
+    }
+
     /// Returns true if the highlight extends across multiple lines.
     #[must_use]
     pub fn is_multiline(&self) -> bool {
@@ -114,6 +202,61 @@ impl Highlight {
                 || self.span.end().page.column != 0))
     }
 
+    /// Returns true if the highlight has an end message to display on a
+    /// continuation line after the message line for the given line number.
+    /// This occurs for single-line highlights with both start and end
+    /// messages.
+    #[must_use]
+    pub fn has_continuation_for_line(&self, line: usize) -> bool {
+        self.span.start().page.line == line
+            && self.span.end().page.line == line
+            && self.start_message.is_some()
+            && self.end_message.is_some()
+    }
+
+    /// Returns true if the given highlight has the same span, message type,
+    /// and messages as this one.
+    #[must_use]
+    pub fn is_identical(&self, other: &Self) -> bool {
+        self.span == other.span
+            && self.message_type == other.message_type
+            && self.start_message == other.start_message
+            && self.end_message == other.end_message
+    }
+
+    /// Returns true if this highlight and the given highlight are both
+    /// single-line highlights on the given line which start at the same
+    /// column. The messages for such highlights would overlap if written
+    /// separately. Empty highlights are never aligned, as their insertion
+    /// glyphs already keep their messages apart.
+    #[must_use]
+    pub fn is_aligned_with(&self, other: &Self, line: usize) -> bool {
+        let single_line = |hl: &Self| !hl.span.is_empty()
+            && hl.span.start().page.line == line
+            && hl.span.end().page.line == line
+            && hl.has_message_for_line(line)
+            && !hl.has_continuation_for_line(line);
+
+        single_line(self)
+            && single_line(other)
+            && self.span.start().page.column
+                == other.span.start().page.column
+    }
+
+    /// Returns true if the highlight has a message to display below the
+    /// highlighted lines.
+    #[must_use]
+    pub fn has_message_below(&self) -> bool {
+        self.message_below && self.is_multiline() && self.end_message.is_some()
+    }
+
+    /// Returns the glyph used to underline the span.
+    fn underline(&self) -> String {
+        self.underline_glyph.map_or_else(
+            || self.message_type.underline().to_string(),
+            String::from)
+    }
+
     /// Writes the riser symbol for the given line number.
     pub(in crate) fn write_riser_for_line<W>(
         &self,
@@ -174,6 +317,141 @@ impl Highlight {
         }
     }
 
+    /// Writes the riser symbol for a continuation line, leaving the riser
+    /// state unchanged.
+    pub(in crate) fn write_continuation_riser<W>(
+        &self,
+        out: &mut W,
+        riser_state: RiserState,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        match riser_state {
+            RiserState::Unused  => Ok(()),
+            RiserState::Started => if color_enabled {
+                write!(out, "{}", "|".color(self.message_type.color()))
+            } else {
+                write!(out, "|")
+            },
+            RiserState::Waiting |
+            RiserState::Ended   => write!(out, " "),
+        }
+    }
+
+    /// Writes the end message continuation line for a single-line highlight
+    /// with both start and end messages. The end message is aligned under the
+    /// end of the span.
+    pub(in crate) fn write_continuation_for_line<W>(
+        &self,
+        out: &mut W,
+        write_extra_riser_spacer: bool,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        let Some(msg) = &self.end_message else { return Ok(()); };
+
+        if write_extra_riser_spacer { write!(out, " ")?; }
+        let column = std::cmp::max(
+            self.span.start().page.column,
+            self.span.end().page.column.saturating_sub(1));
+        for _ in 0..column {
+            write!(out, " ")?;
+        }
+        if color_enabled {
+            writeln!(out, "{} {}",
+                "|".color(self.message_type.color()),
+                msg.color(self.message_type.color()))
+        } else {
+            writeln!(out, "| {msg}")
+        }
+    }
+
+    /// Writes the underline for a group of aligned highlights, extending
+    /// from the start of this highlight's span to the given end column. No
+    /// message is written.
+    pub(in crate) fn write_aligned_underline<W>(
+        &self,
+        out: &mut W,
+        end_column: usize,
+        write_extra_riser_spacer: bool,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        if write_extra_riser_spacer { write!(out, " ")?; }
+        let start_column = self.span.start().page.column;
+        for _ in 0..start_column {
+            write!(out, " ")?;
+        }
+        if end_column <= start_column {
+            let glyph = self.insertion_glyph.to_string();
+            if color_enabled {
+                writeln!(out, "{}", glyph.color(self.message_type.color()))
+            } else {
+                writeln!(out, "{glyph}")
+            }
+        } else {
+            let underline = self.underline();
+            for _ in start_column..end_column {
+                if color_enabled {
+                    write!(out, "{}", underline
+                        .color(self.message_type.color()))?;
+                } else {
+                    write!(out, "{underline}")?;
+                }
+            }
+            writeln!(out)
+        }
+    }
+
+    /// Writes the connector line joining the underline of a group of aligned
+    /// highlights to their messages.
+    pub(in crate) fn write_aligned_connector<W>(
+        &self,
+        out: &mut W,
+        write_extra_riser_spacer: bool,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        if write_extra_riser_spacer { write!(out, " ")?; }
+        for _ in 0..self.span.start().page.column {
+            write!(out, " ")?;
+        }
+        if color_enabled {
+            writeln!(out, "{}", "|".color(self.message_type.color()))
+        } else {
+            writeln!(out, "|")
+        }
+    }
+
+    /// Writes the message of an aligned single-line highlight, starting at
+    /// the start column of its span.
+    pub(in crate) fn write_aligned_message<W>(
+        &self,
+        out: &mut W,
+        write_extra_riser_spacer: bool,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        let msg = self.start_message.as_ref().or(self.end_message.as_ref());
+        if write_extra_riser_spacer { write!(out, " ")?; }
+        for _ in 0..self.span.start().page.column {
+            write!(out, " ")?;
+        }
+        match msg {
+            Some(msg) => if color_enabled {
+                writeln!(out, "{}", msg.color(self.message_type.color()))
+            } else {
+                writeln!(out, "{msg}")
+            },
+            None => writeln!(out),
+        }
+    }
+
     /// Writes the message text for the given line number.
     #[allow(clippy::too_many_lines)]
     pub(in crate) fn write_message_for_line<W>(
@@ -193,35 +471,35 @@ impl Highlight {
                 write!(out, " ")?;
             }
             if self.span.is_empty() {
+                let glyph = self.insertion_glyph.to_string();
                 if color_enabled {
-                    write!(out, "{}", "\\".color(self.message_type.color()))?;
+                    write!(out, "{}", glyph.color(self.message_type.color()))?;
                 } else {
-                    write!(out, "\\")?;
+                    write!(out, "{glyph}")?;
                 }
             } else {
                 let underline_count = std::cmp::max(
                     self.span.end().page.column.saturating_sub(self.span.start().page.column),
                     1);
+                let underline = self.underline();
                 for _ in 0..underline_count {
                     if color_enabled {
-                        write!(out, "{}", self.message_type
-                            .underline()
+                        write!(out, "{}", underline
                             .color(self.message_type.color()))?;
                     } else {
-                        write!(out, "{}", self.message_type
-                            .underline())?;
+                        write!(out, "{underline}")?;
                     }
                 }
             }
             match (&self.start_message, &self.end_message) {
-                (Some(msg), None)      | 
-                (None,      Some(msg)) => if color_enabled {
+                // If both are present, the end message is written on a
+                // continuation line.
+                (Some(msg), _) | (None, Some(msg)) => if color_enabled {
                     writeln!(out, " {}", msg.color(self.message_type.color()))?;
                 } else {
                     writeln!(out, " {msg}")?;
                 },
-                (Some(_fst), Some(_snd))  => todo!(),
-                (None,       None) => {
+                (None, None) => {
                     writeln!(out)?;
                 },
             }
@@ -252,7 +530,7 @@ impl Highlight {
                 Some(msg) => if color_enabled {
                     writeln!(out, " {}", msg.color(self.message_type.color()))?;
                 } else {
-                    write!(out, " {msg}")?;
+                    writeln!(out, " {msg}")?;
                 },
                 None      => writeln!(out)?,
             }
@@ -280,6 +558,7 @@ impl Highlight {
                 write!(out, "^")?;
             }
             match &self.end_message {
+                Some(_) if self.has_message_below() => writeln!(out)?,
                 Some(msg) => if color_enabled {
                     writeln!(out, " {}", msg.color(self.message_type.color()))?;
                 } else {
@@ -291,4 +570,24 @@ impl Highlight {
         }
         Ok(())
     }
+
+    /// Writes the end message to be displayed below the highlighted lines.
+    pub(in crate) fn write_message_below<W>(
+        &self,
+        out: &mut W,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        if !self.has_message_below() { return Ok(()); }
+
+        if let Some(msg) = &self.end_message {
+            if color_enabled {
+                writeln!(out, "{}", msg.color(self.message_type.color()))?;
+            } else {
+                writeln!(out, "{msg}")?;
+            }
+        }
+        Ok(())
+    }
 }
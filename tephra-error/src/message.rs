@@ -22,8 +22,13 @@ use std::fmt::Display;
 ////////////////////////////////////////////////////////////////////////////////
 /// A `CodeDisplay`, `Note`, or `Highlight` message type. Used to
 /// determine the color and format of the message.
+///
+/// Message types are ordered by severity, from least to most severe: `Debug`,
+/// `Info`, `Note`, `Help`, `Warning`, `Error`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MessageType {
+    /// A diagnostic message for debugging or tracing.
+    Debug,
     /// An informational message.
     Info,
     /// An error message.
@@ -42,6 +47,7 @@ impl MessageType {
     pub fn color(self) -> Color {
         use MessageType::*;
         match self {
+            Debug   => Color::BrightBlack,
             Info    => Color::BrightWhite,
             Error   => Color::BrightRed,
             Warning => Color::BrightYellow,
@@ -50,6 +56,52 @@ impl MessageType {
         }
     }
 
+    /// Returns the severity of the message type. Higher values are more
+    /// severe.
+    #[must_use]
+    pub fn severity(self) -> u8 {
+        use MessageType::*;
+        match self {
+            Debug   => 0,
+            Info    => 1,
+            Note    => 2,
+            Help    => 3,
+            Warning => 4,
+            Error   => 5,
+        }
+    }
+
+    /// Returns the name of the message type.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        use MessageType::*;
+        match self {
+            Debug   => "debug",
+            Info    => "info",
+            Error   => "error",
+            Warning => "warning",
+            Note    => "note",
+            Help    => "help",
+        }
+    }
+
+    /// Returns the Language Server Protocol `DiagnosticSeverity` of the message
+    /// type.
+    #[cfg(feature = "lsp")]
+    #[must_use]
+    pub fn lsp_severity(self) -> lsp_types::DiagnosticSeverity {
+        use lsp_types::DiagnosticSeverity;
+        use MessageType::*;
+        match self {
+            Error   => DiagnosticSeverity::ERROR,
+            Warning => DiagnosticSeverity::WARNING,
+            Info    => DiagnosticSeverity::INFORMATION,
+            Note    |
+            Help    |
+            Debug   => DiagnosticSeverity::HINT,
+        }
+    }
+
     /// Returns the underline associated with the message type.
     #[must_use]
     pub fn underline(self) -> &'static str {
@@ -60,6 +112,7 @@ impl MessageType {
             Info    |
             Note    => "-",
             Help    => "~",
+            Debug   => ".",
         }
     }
 
@@ -74,6 +127,7 @@ impl MessageType {
         if color_enabled {
             let color = self.color();
             match self {
+                Debug   => write!(out, "{}", "debug".color(color)),
                 Info    => write!(out, "info"),
                 Error   => write!(out, "{}", "error".color(color).bold()),
                 Warning => write!(out, "{}", "warning".color(color).bold()),
@@ -81,17 +135,23 @@ impl MessageType {
                 Help    => write!(out, "{}", "help".color(color).bold()),
             }
         } else {
-            match self {
-                Info    => write!(out, "info"),
-                Error   => write!(out, "error"),
-                Warning => write!(out, "warning"),
-                Note    => write!(out, "note"),
-                Help    => write!(out, "help"),
-            }
+            write!(out, "{}", self.name())
         }
     }
 }
 
+impl PartialOrd for MessageType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MessageType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.severity().cmp(&other.severity())
+    }
+}
+
 impl Display for MessageType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.write_with_color_enablement(f, true)
@@ -9,7 +9,12 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Internal library imports.
+use crate::Highlight;
 use crate::MessageType;
+use crate::SpanDisplay;
+
+// External library imports.
+use tephra_span::SourceTextRef;
 
 // Standard library imports.
 use std::fmt::Display;
@@ -23,12 +28,47 @@ use std::fmt::Write;
 #[derive(Debug, Clone)]
 pub struct Note {
     /// The message type for the note.
-    pub(in crate) note_type: MessageType,
-    /// The note to display.
-    pub(in crate) note: String,
+    pub(in crate) message_type: MessageType,
+    /// The note text to display.
+    pub(in crate) text: String,
+    /// A highlighted span to display beneath the note.
+    pub(in crate) highlight: Option<Highlight>,
 }
 
 impl Note {
+    /// Constructs a new `Note` with the given `MessageType` and text.
+    #[must_use]
+    pub fn new<M>(note_type: MessageType, note: M) -> Self
+        where M: Into<String>,
+    {
+        Self {
+            message_type: note_type,
+            text: note.into(),
+            highlight: None,
+        }
+    }
+
+    /// Returns the given `Note` with the given `Highlight`. The highlighted
+    /// source text is displayed beneath the note.
+    #[must_use]
+    pub fn with_highlight(mut self, highlight: Highlight) -> Self {
+        self.highlight = Some(highlight);
+        self
+    }
+
+    /// Returns true if the given note has the same message type, text, and
+    /// highlight as this one.
+    #[must_use]
+    pub fn is_identical(&self, other: &Self) -> bool {
+        self.message_type == other.message_type
+            && self.text == other.text
+            && match (&self.highlight, &other.highlight) {
+                (Some(a), Some(b)) => a.is_identical(b),
+                (None, None)       => true,
+                _                  => false,
+            }
+    }
+
     pub(in crate) fn write_with_color_enablement<W>(
         &self,
         out: &mut W,
@@ -36,8 +76,36 @@ impl Note {
         -> std::fmt::Result
         where W: Write
     {
-        self.note_type.write_with_color_enablement(out, color_enabled)?;
-        write!(out, ": {}", self.note)
+        self.message_type.write_with_color_enablement(out, color_enabled)?;
+        write!(out, ": {}", self.text)
+    }
+
+    /// Writes the source text highlighted by the note, if any.
+    pub(in crate) fn write_highlight_with_color_enablement<W>(
+        &self,
+        out: &mut W,
+        source_text: SourceTextRef<'_>,
+        color_enabled: bool)
+        -> std::fmt::Result
+        where W: Write
+    {
+        let Some(highlight) = &self.highlight else { return Ok(()); };
+
+        SpanDisplay::new(source_text, highlight.span())
+            .with_highlight(highlight.clone())
+            .write_with_color_enablement(out, source_text, color_enabled)
+    }
+}
+
+impl From<&str> for Note {
+    fn from(note: &str) -> Self {
+        Self::new(MessageType::Note, note)
+    }
+}
+
+impl From<String> for Note {
+    fn from(note: String) -> Self {
+        Self::new(MessageType::Note, note)
     }
 }
 
@@ -23,17 +23,26 @@ use std::sync::RwLock;
 // Ok(true) => recover is finished, resume parsing
 // Ok(false) => recover is not finished, keep advancing
 // Err(&str) => recover failed, 
+//
+// The function is called with `None` once the end of the text is reached. The
+// recovery fails regardless of the result, so any recovery state should be
+// reset to allow the `Recover` to be reused.
 
 pub type Recover<T>
-    = Rc<RwLock<dyn FnMut(T) -> Result<bool, RecoverError>>>;
+    = Rc<RwLock<dyn FnMut(Option<T>) -> Result<bool, RecoverError>>>;
 
 pub fn recover_after<T>(token: T) -> Recover<T> 
     where T: PartialEq + Send + Sync + 'static
 {
     let mut found = false;
 
-    Rc::new(RwLock::new(move |next_token| {
+    Rc::new(RwLock::new(move |next_token: Option<T>| {
+        let Some(next_token) = next_token else {
+            found = false;
+            return Ok(false);
+        };
         if found {
+            found = false;
             Ok(true)
         } else {
             found = next_token == token;
@@ -45,8 +54,8 @@ pub fn recover_after<T>(token: T) -> Recover<T>
 pub fn recover_before<T>(token: T) -> Recover<T> 
     where T: PartialEq + Send + Sync + 'static,
 {
-    Rc::new(RwLock::new(move |next_token| {
-        Ok(next_token == token)
+    Rc::new(RwLock::new(move |next_token: Option<T>| {
+        Ok(next_token.is_some_and(|t| t == token))
     }))
 }
 
@@ -59,8 +68,13 @@ pub fn recover_after_any<T, I>(tokens: I) -> Recover<T>
     let mut found = false;
     let tokens: Vec<_> = tokens.into_iter().collect();
 
-    Rc::new(RwLock::new(move |next_token| {
+    Rc::new(RwLock::new(move |next_token: Option<T>| {
+        let Some(next_token) = next_token else {
+            found = false;
+            return Ok(false);
+        };
         if found {
+            found = false;
             Ok(true)
         } else {
             found = tokens.contains(&next_token);
@@ -76,9 +90,41 @@ pub fn recover_before_any<T, I>(tokens: I) -> Recover<T>
 {
     let tokens: Vec<_> = tokens.into_iter().collect();
 
-    Rc::new(RwLock::new(move |next_token| {
-        Ok(tokens.contains(&next_token))
+    Rc::new(RwLock::new(move |next_token: Option<T>| {
+        Ok(next_token.is_some_and(|t| tokens.contains(&t)))
     }))
 }
 
-
+/// Returns a `Recover` which stops before the first of the `close_tokens` that
+/// is not matched by a preceding token from `open_tokens`.
+///
+/// Nesting depth is tracked while skipping tokens, so a recovery within a
+/// bracketed section skips over any nested brackets and stops before the
+/// close token of the section itself. The nesting depth is reset once the
+/// recovery finishes or reaches the end of the text, so the `Recover` may be
+/// reused.
+pub fn recover_to_balanced<T, I, J>(open_tokens: I, close_tokens: J)
+    -> Recover<T>
+    where
+        T: PartialEq + Send + Sync + 'static,
+        I: IntoIterator<Item=T>,
+        J: IntoIterator<Item=T>,
+{
+    let open_tokens: Vec<_> = open_tokens.into_iter().collect();
+    let close_tokens: Vec<_> = close_tokens.into_iter().collect();
+    let mut depth: usize = 0;
+
+    Rc::new(RwLock::new(move |next_token: Option<T>| {
+        let Some(next_token) = next_token else {
+            depth = 0;
+            return Ok(false);
+        };
+        if open_tokens.contains(&next_token) {
+            depth += 1;
+        } else if close_tokens.contains(&next_token) {
+            if depth == 0 { return Ok(true); }
+            depth -= 1;
+        }
+        Ok(false)
+    }))
+}
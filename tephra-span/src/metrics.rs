@@ -12,7 +12,8 @@
 use crate::Pos;
 
 // External library imports.
-// TODO: Use the unicode-segmentation crate instead?
+#[cfg(feature = "grapheme")]
+use unicode_segmentation::UnicodeSegmentation as _;
 use unicode_width::UnicodeWidthChar;
 
 
@@ -26,6 +27,9 @@ pub const DEFAULT_LINE_ENDING: LineEnding = LineEnding::Lf;
 /// The default tab width.
 pub const DEFAULT_TAB_WIDTH: u8 = 4;
 
+/// The default `CharWidth`.
+pub const DEFAULT_CHAR_WIDTH: CharWidth = CharWidth::Unicode;
+
 /// The byte size of a tab character.
 const TAB_LEN_UTF8: usize = '\t'.len_utf8();
 
@@ -64,6 +68,63 @@ impl LineEnding {
 }
 
 
+////////////////////////////////////////////////////////////////////////////////
+// CharWidth
+////////////////////////////////////////////////////////////////////////////////
+/// Character width measurements used to track column positioning in the lexer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum CharWidth {
+    /// Characters are measured by their unicode display width.
+    Unicode,
+    /// Every character is one column wide. This avoids unicode width lookups,
+    /// but is incorrect for non-ASCII text.
+    Fixed,
+    /// Every byte is one column wide, and tabs are not expanded. Columns are
+    /// equal to the byte offset within the line.
+    Bytes,
+    /// Every UTF-16 code unit is one column wide, and tabs are not expanded.
+    /// Columns are equal to the UTF-16 offset within the line, as used by
+    /// language server clients.
+    Utf16,
+    /// Every extended grapheme cluster is one column wide, so positions
+    /// advance over a full cluster at a time. A single `char` measured alone
+    /// is one column wide unless it has zero display width.
+    ///
+    /// Grapheme clusters are only recognized with the `grapheme` feature.
+    /// Without it, positions advance one `char` at a time.
+    Grapheme,
+}
+
+impl Default for CharWidth {
+    fn default() -> Self {
+        DEFAULT_CHAR_WIDTH
+    }
+}
+
+impl CharWidth {
+    /// Returns the column width of the given `char`.
+    #[must_use]
+    pub fn width(self, c: char) -> usize {
+        match self {
+            Self::Unicode => UnicodeWidthChar::width(c).unwrap_or(0),
+            Self::Fixed   => 1,
+            Self::Bytes   => c.len_utf8(),
+            Self::Utf16   => c.len_utf16(),
+            Self::Grapheme => std::cmp::min(
+                UnicodeWidthChar::width(c).unwrap_or(0),
+                1),
+        }
+    }
+
+    /// Returns true if tabs are expanded to the next tab stop.
+    #[must_use]
+    pub fn expands_tabs(self) -> bool {
+        !matches!(self, Self::Bytes | Self::Utf16)
+    }
+}
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // ColumnMetrics
 ////////////////////////////////////////////////////////////////////////////////
@@ -74,6 +135,8 @@ pub struct ColumnMetrics {
     pub line_ending: LineEnding,
     /// The source tab width.
     pub tab_width: u8,
+    /// The source character width measurement.
+    pub char_width: CharWidth,
 }
 
 impl Default for ColumnMetrics {
@@ -89,6 +152,7 @@ impl ColumnMetrics {
         Self {
             line_ending: DEFAULT_LINE_ENDING,
             tab_width: DEFAULT_TAB_WIDTH,
+            char_width: DEFAULT_CHAR_WIDTH,
         }
     }
 
@@ -106,6 +170,13 @@ impl ColumnMetrics {
         self
     }
 
+    /// Sets the character width measurement for the Lexer.
+    #[must_use]
+    pub fn with_char_width(mut self, char_width: CharWidth) -> Self {
+        self.char_width = char_width;
+        self
+    }
+
     /// Returns the next column-aligned position after the given base position
     /// within the given text. None is returned if the result position is not
     /// within the text.
@@ -125,7 +196,7 @@ impl ColumnMetrics {
 
         let mut chars = text[base.byte..].chars();
         match chars.next() {
-            Some(c) if c == '\t' => {
+            Some(c) if c == '\t' && self.char_width.expands_tabs() => {
                 let tab = self.tab_width as usize;
                 let tab_stop = tab - (base.page.column % tab);
                 let new_pos = Pos::new(
@@ -136,11 +207,30 @@ impl ColumnMetrics {
                 Some(new_pos)
             },
 
+            #[cfg(feature = "grapheme")]
+            Some(_) if self.char_width == CharWidth::Grapheme => {
+                let cluster = text[base.byte..]
+                    .graphemes(true)
+                    .next()
+                    .expect("grapheme for nonempty text");
+                // A cluster may contain a line break, such as "\r\n" when
+                // lines end in '\n', so it is split before the line break.
+                let len = cluster
+                    .find(line_break)
+                    .filter(|idx| *idx > 0)
+                    .unwrap_or(cluster.len());
+                let new_pos = Pos::new(
+                    base.byte + len,
+                    base.page.line,
+                    base.page.column + 1);
+                Some(new_pos)
+            },
+
             Some(c) => {
                 let new_pos = Pos::new(
                     base.byte + c.len_utf8(),
                     base.page.line,
-                    base.page.column + UnicodeWidthChar::width(c).unwrap_or(0));
+                    base.page.column + self.char_width.width(c));
                 Some(new_pos)
             },
 
@@ -168,7 +258,7 @@ impl ColumnMetrics {
 
         let mut chars = text[..base.byte].chars();
         match chars.next_back() {
-            Some(c) if c == '\t' => {
+            Some(c) if c == '\t' && self.char_width.expands_tabs() => {
                 // To get position of tab start, we must measure from the start
                 // of the line.
 
@@ -186,11 +276,31 @@ impl ColumnMetrics {
                 Some(new_pos)
             },
 
+            #[cfg(feature = "grapheme")]
+            Some(_) if self.char_width == CharWidth::Grapheme => {
+                let cluster = text[..base.byte]
+                    .graphemes(true)
+                    .next_back()
+                    .expect("grapheme for nonempty text");
+                // A cluster may contain a line break, such as "\r\n" when
+                // lines end in '\r', so it is split after the line break.
+                let len = cluster
+                    .rfind(line_break)
+                    .map(|idx| cluster.len() - idx - line_break.len())
+                    .filter(|len| *len > 0)
+                    .unwrap_or(cluster.len());
+                let new_pos = Pos::new(
+                    base.byte - len,
+                    base.page.line,
+                    base.page.column - 1);
+                Some(new_pos)
+            },
+
             Some(c) => {
                 let new_pos = Pos::new(
                     base.byte - c.len_utf8(),
                     base.page.line,
-                    base.page.column - UnicodeWidthChar::width(c).unwrap_or(0));
+                    base.page.column - self.char_width.width(c));
                 Some(new_pos)
             },
 
@@ -8,6 +8,9 @@
 //! Character positioning.
 ////////////////////////////////////////////////////////////////////////////////
 
+// Standard library imports.
+use std::cmp::Ordering;
+
 
 ////////////////////////////////////////////////////////////////////////////////
 // Pos
@@ -61,6 +64,16 @@ impl Pos {
         self.page.shift(other.page);
     }
 
+    /// Returns the position as an offset from the given base position. Offsets
+    /// before the base position are clamped to zero.
+    #[must_use]
+    pub fn relative_to(self, base: Self) -> Self {
+        Self {
+            byte: self.byte.saturating_sub(base.byte),
+            page: self.page.relative_to(base.page),
+        }
+    }
+
     pub(in crate) fn with_byte_offset<F>(mut self, offset: usize, f: F)
         -> Option<Self> 
         where F: FnOnce(Self) -> Option<Self>
@@ -126,6 +139,23 @@ impl Page {
         }
     }
 
+    /// Returns the page position as an offset from the given base page
+    /// position. Offsets before the base position are clamped to zero.
+    #[must_use]
+    pub fn relative_to(self, base: Self) -> Self {
+        match self.line.cmp(&base.line) {
+            Ordering::Greater => Self {
+                line: self.line - base.line,
+                column: self.column,
+            },
+            Ordering::Equal => Self {
+                line: 0,
+                column: self.column.saturating_sub(base.column),
+            },
+            Ordering::Less => Self::ZERO,
+        }
+    }
+
     pub fn shift(&mut self, other: Self) {
         self.line += other.line;
         if other.is_line_start() || other.line > 0 {
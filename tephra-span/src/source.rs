@@ -13,6 +13,10 @@ use crate::ColumnMetrics;
 use crate::Pos;
 use crate::Span;
 
+// Standard library imports.
+use std::ops::Range;
+use std::str::Utf8Error;
+
 
 pub const SOURCE_TEXT_DISPLAY_LEN: usize = 12;
 pub const SOURCE_TEXT_DEBUG_LEN: usize = 12;
@@ -32,6 +36,8 @@ pub struct SourceText<T> {
     text: T,
     /// The source text name.
     name: Option<T>,
+    /// Whether the source text is synthetic, rather than read from a file.
+    synthetic: bool,
     /// The column metrics of the source text.
     metrics: ColumnMetrics,
     /// The position of the start of the source text.
@@ -53,6 +59,7 @@ impl<T> SourceText<T> {
         Self {
             text,
             name: None,
+            synthetic: false,
             offset: Pos::ZERO,
             metrics: ColumnMetrics::default(),
         }
@@ -62,6 +69,17 @@ impl<T> SourceText<T> {
         &self.text
     }
 
+    /// Constructs a new synthetic `SourceText` with the given name. Synthetic
+    /// sources represent text which is not read from a file, such as generated
+    /// code or interactive input, and their names are displayed in angle
+    /// brackets.
+    #[must_use]
+    pub fn synthetic(name: T, text: T) -> Self {
+        let mut source = Self::new(text).with_name(name);
+        source.synthetic = true;
+        source
+    }
+
     #[must_use]
     pub fn with_name(mut self, name: T) -> Self {
         self.name = Some(name);
@@ -74,6 +92,11 @@ impl<T> SourceText<T> {
         self
     }
 
+    /// Returns the given `SourceText` with the given start position.
+    ///
+    /// Positions within the text are offset by the start position, so a
+    /// fragment of a larger file can be displayed at its position within that
+    /// file.
     #[must_use]
     pub fn with_start_position(mut self, offset: Pos) -> Self {
         self.offset = offset;
@@ -84,11 +107,29 @@ impl<T> SourceText<T> {
         self.metrics
     }
 
+    /// Returns true if the source text is synthetic.
+    pub fn is_synthetic(&self) -> bool {
+        self.synthetic
+    }
+
     pub fn column_metrics_mut(&mut self) -> &mut ColumnMetrics {
         &mut self.metrics
     }
 }
 
+impl<'text> SourceText<&'text str> {
+    /// Constructs a new `SourceText` from the given bytes, validating that
+    /// they are UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Utf8Error` if the bytes are not valid UTF-8. The error can be
+    /// converted into a diagnostic with `SourceErrorOwned::from_utf8_error`.
+    pub fn from_utf8(bytes: &'text [u8]) -> Result<Self, Utf8Error> {
+        std::str::from_utf8(bytes).map(Self::new)
+    }
+}
+
 impl<T> SourceText<T> where T: AsRef<str> {
     pub fn as_str(&self) -> &'_ str {
         self.text.as_ref()
@@ -172,6 +213,24 @@ impl<T> SourceText<T> where T: AsRef<str> {
             .unwrap()
     }
 
+    /// Returns the position at which the given position should be displayed.
+    ///
+    /// The end of a text ending in a line break lies on an empty line past the
+    /// final line break, so it is displayed at the end of the preceding line
+    /// instead. Other positions are returned unchanged.
+    pub fn display_position(&self, pos: Pos) -> Pos {
+        if pos.page.column != 0
+            || pos.byte == self.offset.byte
+            || pos != self.end_position()
+        {
+            return pos;
+        }
+
+        self.previous_position(pos)
+            .map_or(pos, |prev| self
+                .line_end_position(self.line_start_position(prev)))
+    }
+
     /// Returns the position at the start of the next line after the given base
     /// position.
     pub fn previous_line_end_position(&self, base: Pos) -> Option<Pos> {
@@ -186,6 +245,47 @@ impl<T> SourceText<T> where T: AsRef<str> {
             |b| self.metrics.next_line_start_position(self.as_str(), b))
     }
 
+    /// Returns the position at the given line and column within the source
+    /// text. None is returned if the line is not within the text.
+    ///
+    /// If the column is past the end of the line, the end of the line is
+    /// returned. If the column is within a tab or wide character, the position
+    /// after it is returned.
+    pub fn pos_at(&self, line: usize, column: usize) -> Option<Pos> {
+        let mut pos = self.start_position();
+        if line < pos.page.line { return None; }
+
+        while pos.page.line < line {
+            pos = self.next_line_start_position(pos)?;
+        }
+
+        let end = self.line_end_position(pos);
+        while pos.page.column < column && pos.byte < end.byte {
+            match self.next_position(pos) {
+                Some(next) => pos = next,
+                None       => break,
+            }
+        }
+        Some(pos)
+    }
+
+    /// Returns the span of the given line within the source text, excluding
+    /// its line break. None is returned if the line is not within the text.
+    pub fn line_at(&self, line: usize) -> Option<Span> {
+        self.pos_at(line, 0)
+            .map(|start| Span::enclosing(start, self.line_end_position(start)))
+    }
+
+    /// Returns an iterator over the spans of each line of the source text,
+    /// excluding their line breaks. A text ending in a line break yields a
+    /// final empty line.
+    pub fn lines(&self) -> impl Iterator<Item=Span> + '_ {
+        std::iter::successors(
+                Some(self.start_position()),
+                |&start| self.next_line_start_position(start))
+            .map(|start| Span::enclosing(start, self.line_end_position(start)))
+    }
+
     /// Returns the position after the given pattern string, given its start
     /// position.
     pub fn position_after_str(&self, start: Pos, pattern: &str)
@@ -232,6 +332,7 @@ impl<T> SourceText<T> where T: AsRef<str> {
             name: self.name(),
             offset: self.offset,
             metrics: self.metrics,
+            synthetic: self.synthetic,
         }
     }
 
@@ -243,8 +344,85 @@ impl<T> SourceText<T> where T: AsRef<str> {
             name: self.name.as_ref().map(|s| s.as_ref().into()),
             offset: self.offset,
             metrics: self.metrics,
+            synthetic: self.synthetic,
         }
     }
+
+    /// Replaces the text in the given byte range with the replacement text,
+    /// returning the edited `SourceTextOwned` and a function for mapping spans
+    /// in the original text to spans in the edited text.
+    ///
+    /// The byte range is given in the same coordinates as the text's spans.
+    /// Spans ending at or before the start of the edit are returned unchanged,
+    /// spans starting after the edit are shifted, and spans overlapping the
+    /// edit are invalidated and mapped to `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte range is out of bounds or does not lie on `char`
+    /// boundaries.
+    pub fn apply_edit(&self, range: Range<usize>, replacement: &str)
+        -> (SourceTextOwned, impl Fn(Span) -> Option<Span>)
+    {
+        let offset = self.offset;
+        let metrics = self.metrics;
+        let old_text = self.as_str();
+        let s = range.start - offset.byte;
+        let e = range.end - offset.byte;
+
+        let mut new_text = String::with_capacity(
+            old_text.len() - (e - s) + replacement.len());
+        new_text.push_str(&old_text[..s]);
+        new_text.push_str(replacement);
+        new_text.push_str(&old_text[e..]);
+        let new_text: Box<str> = new_text.into();
+
+        let start_rel = metrics.end_position(&old_text[..s], Pos::ZERO);
+        let old_end_rel = metrics.end_position(&old_text[..e], start_rel);
+        let new_end_rel = metrics.end_position(
+            &new_text[..s + replacement.len()],
+            start_rel);
+
+        let edit_start = range.start;
+        let edit_end = range.end;
+        let old_end = offset.shifted(old_end_rel);
+        let new_end = offset.shifted(new_end_rel);
+        let text = new_text.clone();
+
+        let map_pos = move |pos: Pos| {
+            if pos.byte <= edit_start { return pos; }
+
+            let byte = pos.byte - old_end.byte + new_end.byte;
+            if pos.page.line > old_end.page.line {
+                // Lines after the edit only shift by line.
+                Pos::new(
+                    byte,
+                    pos.page.line - old_end.page.line + new_end.page.line,
+                    pos.page.column)
+            } else {
+                // The edit line must be re-measured to account for tabs.
+                offset.shifted(metrics.end_position(
+                    &text[..byte - offset.byte],
+                    new_end_rel))
+            }
+        };
+
+        let remap = move |span: Span| {
+            if span.start().byte < edit_end && span.end().byte > edit_start {
+                return None;
+            }
+            Some(Span::enclosing(map_pos(span.start()), map_pos(span.end())))
+        };
+
+        let source = SourceText {
+            text: new_text,
+            name: self.name.as_ref().map(|s| s.as_ref().into()),
+            offset: self.offset,
+            metrics: self.metrics,
+            synthetic: self.synthetic,
+        };
+        (source, remap)
+    }
 }
 
 impl<'text, T> SourceText<T>
@@ -263,6 +441,7 @@ impl<'text, T> SourceText<T>
             text: T::from(&self.as_str()[s..e]),
             name: self.name.as_ref().map(|n| T::from(n.as_ref())),
             metrics: self.metrics,
+            synthetic: self.synthetic,
             offset: span.start(),
         }
     }
@@ -302,6 +481,7 @@ impl<T> std::fmt::Debug for SourceText<T>  where T: AsRef<str> {
         f.debug_struct("SourceText")
             .field("text", &src)
             .field("name", &self.name.as_ref().map(std::convert::AsRef::as_ref))
+            .field("synthetic", &self.synthetic)
             .field("offset", &self.offset)
             .field("metrics", &self.metrics)
             .finish()
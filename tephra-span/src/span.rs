@@ -108,12 +108,66 @@ impl Span {
         self.byte.len()
     }
 
+    /// Returns a `Display` adapter which formats only the byte range of the
+    /// span.
+    #[must_use]
+    pub fn display_bytes(&self) -> DisplayBytes {
+        DisplayBytes(self.byte)
+    }
+
+    /// Returns the start and end positions of the span as offsets from the
+    /// given base position. Offsets before the base position are clamped to
+    /// zero.
+    #[must_use]
+    pub fn relative_to(&self, base: Pos) -> (Pos, Pos) {
+        (self.start().relative_to(base), self.end().relative_to(base))
+    }
+
+    /// Returns the span translated by the given position delta. This is the
+    /// inverse of `relative_to`: the span is treated as relative to a text
+    /// beginning at `delta`, such as a substring spliced into a larger text.
+    ///
+    /// Lines are offset by the delta's line. Columns are only offset by the
+    /// delta's column for positions on the span's first line of text.
+    #[must_use]
+    pub fn translated_by(&self, delta: Pos) -> Self {
+        Self::enclosing(
+            delta.shifted(self.start()),
+            delta.shifted(self.end()))
+    }
+
+    /// Returns the span moved to begin at the given start position, preserving
+    /// its width in bytes, lines, and columns.
+    #[must_use]
+    pub fn reanchored(&self, new_start: Pos) -> Self {
+        let width = self.end().relative_to(self.start());
+        Self::enclosing(new_start, new_start.shifted(width))
+    }
+
     /// Returns true if the given position is contained within the span.
     ///
-    /// This will return true if the position is a boundary point of the span.
+    /// Containment is half-open: the start position is contained, but the end
+    /// position is not. An empty span therefore contains no positions.
     #[must_use]
-    pub fn contains(&self, pos: &Pos) -> bool {
-        *pos >= self.start()  && *pos <= self.end()
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos >= self.start() && pos < self.end()
+    }
+
+    /// Returns true if the given span lies entirely within the span.
+    ///
+    /// Unlike [`contains`], the end positions are compared inclusively, so
+    /// every span contains itself and any empty span on its boundary.
+    ///
+    /// [`contains`]: Self::contains
+    #[must_use]
+    pub fn contains_span(&self, other: Self) -> bool {
+        other.start() >= self.start() && other.end() <= self.end()
+    }
+
+    /// Returns true if the given position is contained within the span or is
+    /// one of its boundary points.
+    fn touches(&self, pos: &Pos) -> bool {
+        *pos >= self.start() && *pos <= self.end()
     }
 
     /// Widens the span on the left and right to the nearest newline.
@@ -128,6 +182,27 @@ impl Span {
             source.line_end_position(self.end()))
     }
 
+    /// Narrows the span on the left and right to exclude whitespace. A span
+    /// containing only whitespace is narrowed to an empty span at its start.
+    #[must_use]
+    pub fn narrow_to_content(&self, source: SourceTextRef<'_>) -> Self {
+        let start = source
+            .position_after_chars_matching(self.start(), char::is_whitespace)
+            .unwrap_or_else(|| self.start());
+        if start.byte >= self.end().byte {
+            return Self::at(self.start());
+        }
+
+        let offset = source.start_position().byte;
+        let mut end = self.end();
+        while let Some(prev) = source.previous_position(end) {
+            let text = &source.as_str()[prev.byte - offset..end.byte - offset];
+            if !text.chars().all(char::is_whitespace) { break; }
+            end = prev;
+        }
+        Self::enclosing(start, end)
+    }
+
     /// Returns true if the given spans overlap.
     ///
     /// This will return true if the boundary points of the spans overlap.
@@ -135,10 +210,10 @@ impl Span {
         where S: Into<Self>,
     {
         let other = other.into();
-        self.contains(&other.start()) ||
-        self.contains(&other.end()) ||
-        other.contains(&self.start()) ||
-        other.contains(&self.end())
+        self.touches(&other.start()) ||
+        self.touches(&other.end()) ||
+        other.touches(&self.start()) ||
+        other.touches(&self.end())
     }
 
     /// Returns true if the given spans share a boundary point without
@@ -179,10 +254,22 @@ impl Span {
     }
 
     /// Returns the overlapping portion the spans.
-    #[allow(clippy::match_same_arms)]
+    #[deprecated(note = "use `Span::intersection` instead")]
     #[must_use]
     pub fn intersect<S>(&self, other: S) -> Option<Self>
         where S: Into<Self>,
+    {
+        self.intersection(other)
+    }
+
+    /// Returns the overlapping portion the spans.
+    ///
+    /// Returns `None` if the spans are disjoint, and an empty span if they
+    /// only share a boundary point.
+    #[allow(clippy::match_same_arms)]
+    #[must_use]
+    pub fn intersection<S>(&self, other: S) -> Option<Self>
+        where S: Into<Self>,
     {
         let other = other.into();
         let a_start = self.start();
@@ -190,14 +277,14 @@ impl Span {
         let a_end = self.end();
         let b_end = other.end();
 
-        let start = match (self.contains(&b_start), other.contains(&a_start)) {
+        let start = match (self.touches(&b_start), other.touches(&a_start)) {
             (true,  true)  => a_start, // Starts coincide.
             (true,  false) => b_start,
             (false, true)  => a_start,
             (false, false) => return None,
         };
 
-        let end = match (self.contains(&b_end), other.contains(&a_end)) {
+        let end = match (self.touches(&b_end), other.touches(&a_end)) {
             (true,  true)  => a_end, // Ends coincide.
             (true,  false) => b_end,
             (false, true)  => a_end,
@@ -233,6 +320,29 @@ impl Span {
         Few::from((l, r))
     }
 
+    /// Splits the span into two adjacent spans at the given byte position.
+    ///
+    /// Returns `None` if the byte position is outside of the span or is not
+    /// at a column-aligned position within the source text.
+    #[must_use]
+    pub fn split_at(&self, byte: usize, source: SourceTextRef<'_>)
+        -> Option<(Self, Self)>
+    {
+        if byte < self.start().byte || byte > self.end().byte {
+            return None;
+        }
+
+        let mut split = self.start();
+        while split.byte < byte {
+            split = source.next_position(split)?;
+        }
+        if split.byte != byte { return None; }
+
+        Some((
+            Self::enclosing(self.start(), split),
+            Self::enclosing(split, self.end())))
+    }
+
     /// Returns an iterator over the lines of the span.
     #[must_use]
     pub fn split_lines<'text>(&self, source: SourceTextRef<'text>)
@@ -300,6 +410,25 @@ impl std::fmt::Display for ByteSpan {
 }
 
 
+////////////////////////////////////////////////////////////////////////////////
+// DisplayBytes
+////////////////////////////////////////////////////////////////////////////////
+/// A `Display` adapter for the byte range of a `Span`. Returned by the
+/// `display_bytes` method on `Span`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplayBytes(ByteSpan);
+
+impl std::fmt::Display for DisplayBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            write!(f, "byte {}", self.0)
+        } else {
+            write!(f, "bytes {}", self.0)
+        }
+    }
+}
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // PageSpan
 ////////////////////////////////////////////////////////////////////////////////
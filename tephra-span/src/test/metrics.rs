@@ -9,6 +9,7 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Internal library imports.
+use crate::CharWidth;
 use crate::ColumnMetrics;
 use crate::Pos;
 
@@ -76,3 +77,135 @@ fn lf_position_after_chars_matching() {
     let expected = Some(Pos::new(6, 2, 8));
     assert_eq!(actual, expected);
 }
+
+/// Tests `ColumnMetrics::end_position` for `CharWidth::Fixed`.
+#[test]
+fn fixed_width_end_position() {
+    let text = "ab\tc\n\u{FF21}d";
+    let metrics = ColumnMetrics::new()
+        .with_char_width(CharWidth::Fixed);
+
+    let actual = metrics.end_position(text, Pos::ZERO);
+    let expected = Pos::new(text.len(), 1, 2);
+    assert_eq!(actual, expected);
+
+    let metrics = ColumnMetrics::new();
+
+    let actual = metrics.end_position(text, Pos::ZERO);
+    let expected = Pos::new(text.len(), 1, 3);
+    assert_eq!(actual, expected);
+}
+
+/// Tests `ColumnMetrics::end_position` for `CharWidth::Bytes`.
+#[test]
+fn bytes_end_position() {
+    let text = "ab\tc\n\u{FF21}\td";
+    let metrics = ColumnMetrics::new()
+        .with_char_width(CharWidth::Bytes);
+
+    let actual = metrics.end_position(text, Pos::ZERO);
+    let expected = Pos::new(text.len(), 1, 5);
+    assert_eq!(actual, expected);
+}
+
+/// Tests `ColumnMetrics::end_position` for `CharWidth::Utf16`.
+#[test]
+fn utf16_end_position() {
+    let text = "a\u{1F600}\tb\n\u{FF21}\u{1F600}c";
+    let metrics = ColumnMetrics::new()
+        .with_char_width(CharWidth::Utf16);
+
+    let actual = metrics.end_position(text, Pos::ZERO);
+    let expected = Pos::new(text.len(), 1, 4);
+    assert_eq!(actual, expected);
+
+    let actual = metrics.next_position(text, Pos::new(1, 0, 1));
+    let expected = Some(Pos::new(5, 0, 3));
+    assert_eq!(actual, expected);
+}
+
+/// Tests `ColumnMetrics::previous_position` for `CharWidth::Utf16`.
+#[test]
+fn utf16_previous_position() {
+    let text = "a\u{1F600}\tb";
+    let metrics = ColumnMetrics::new()
+        .with_char_width(CharWidth::Utf16);
+
+    let mut pos = metrics.end_position(text, Pos::ZERO);
+    assert_eq!(pos, Pos::new(7, 0, 5));
+
+    let mut positions = Vec::new();
+    while let Some(prev) = metrics.previous_position(text, pos) {
+        let next = metrics.next_position(text, prev);
+        assert_eq!(next, Some(pos));
+        positions.push(prev);
+        pos = prev;
+    }
+
+    let expected = vec![
+        Pos::new(6, 0, 4),
+        Pos::new(5, 0, 3),
+        Pos::new(1, 0, 1),
+        Pos::new(0, 0, 0),
+    ];
+    assert_eq!(positions, expected);
+}
+
+/// Tests `ColumnMetrics::next_position` for `CharWidth::Grapheme` with a flag
+/// emoji.
+#[cfg(feature = "grapheme")]
+#[test]
+fn grapheme_next_position_flag() {
+    let text = "a\u{1F1FA}\u{1F1F8}b";
+    let metrics = ColumnMetrics::new()
+        .with_char_width(CharWidth::Grapheme);
+
+    let actual = metrics.next_position(text, Pos::new(1, 0, 1));
+    let expected = Some(Pos::new(9, 0, 2));
+    assert_eq!(actual, expected);
+
+    let actual = metrics.end_position(text, Pos::ZERO);
+    let expected = Pos::new(text.len(), 0, 3);
+    assert_eq!(actual, expected);
+}
+
+/// Tests `ColumnMetrics::next_position` for `CharWidth::Grapheme` without the
+/// `grapheme` feature, which advances one `char` at a time.
+#[cfg(not(feature = "grapheme"))]
+#[test]
+fn grapheme_next_position_flag_fallback() {
+    let text = "a\u{1F1FA}\u{1F1F8}b";
+    let metrics = ColumnMetrics::new()
+        .with_char_width(CharWidth::Grapheme);
+
+    let actual = metrics.next_position(text, Pos::new(1, 0, 1));
+    let expected = Some(Pos::new(5, 0, 2));
+    assert_eq!(actual, expected);
+}
+
+/// Tests `ColumnMetrics::previous_position` for `CharWidth::Grapheme` with a
+/// combining accent.
+#[cfg(feature = "grapheme")]
+#[test]
+fn grapheme_previous_position_combining() {
+    let text = "e\u{301}x";
+    let metrics = ColumnMetrics::new()
+        .with_char_width(CharWidth::Grapheme);
+
+    let mut pos = metrics.end_position(text, Pos::ZERO);
+    assert_eq!(pos, Pos::new(4, 0, 2));
+
+    let mut positions = Vec::new();
+    while let Some(prev) = metrics.previous_position(text, pos) {
+        let next = metrics.next_position(text, prev);
+        assert_eq!(next, Some(pos));
+        positions.push(prev);
+        pos = prev;
+    }
+
+    let expected = vec![
+        Pos::new(3, 0, 1),
+        Pos::new(0, 0, 0),
+    ];
+    assert_eq!(positions, expected);
+}
@@ -138,6 +138,88 @@ fn span_basic_full_widen_to_line() {
 }
 
 
+/// Tests `Span::narrow_to_content`.
+#[test]
+fn span_basic_narrow_to_content() {
+    const TEXT: &str = " \n  abcd  \n ";
+    let source = SourceText::new(TEXT);
+    let span = Span::enclosing(
+            Pos::new(2, 1, 0),
+            Pos::new(10, 1, 8))
+        .narrow_to_content(source);
+
+    // Check text clip.
+    let actual = source.clipped(span);
+    let expected = "abcd";
+    assert_eq!(actual.as_ref(), expected);
+
+    // Check span display.
+    let actual = format!("{span}");
+    let expected = "1:2-1:6, bytes 4-8";
+    assert_eq!(actual, expected);
+}
+
+/// Tests `Span::split_at` within a line.
+#[test]
+fn span_basic_split_at() {
+    const TEXT: &str = "abc\ndefg\nhij";
+    let source = SourceText::new(TEXT);
+    let span = Span::enclosing(
+        Pos::new(1, 0, 1),
+        Pos::new(10, 2, 1));
+
+    let (left, right) = span.split_at(6, source)
+        .expect("split within span");
+
+    // Check text clips.
+    assert_eq!(source.clipped(left).as_ref(), "bc\nde");
+    assert_eq!(source.clipped(right).as_ref(), "fg\nh");
+
+    // Check span displays.
+    assert_eq!(format!("{left}"), "0:1-1:2, bytes 1-6");
+    assert_eq!(format!("{right}"), "1:2-2:1, bytes 6-10");
+}
+
+/// Tests `Span::split_at` at a line break.
+#[test]
+fn span_basic_split_at_line_break() {
+    const TEXT: &str = "abc\ndefg\nhij";
+    let source = SourceText::new(TEXT);
+    let span = Span::enclosing(
+        Pos::new(1, 0, 1),
+        Pos::new(10, 2, 1));
+
+    let (left, right) = span.split_at(3, source)
+        .expect("split within span");
+
+    // Check text clips.
+    assert_eq!(source.clipped(left).as_ref(), "bc");
+    assert_eq!(source.clipped(right).as_ref(), "\ndefg\nh");
+
+    // Check span displays.
+    assert_eq!(format!("{left}"), "0:1-0:3, bytes 1-3");
+    assert_eq!(format!("{right}"), "0:3-2:1, bytes 3-10");
+
+    // Check positions outside of the span.
+    assert_eq!(span.split_at(0, source), None);
+    assert_eq!(span.split_at(11, source), None);
+}
+
+/// Tests `Span::narrow_to_content` on a blank line.
+#[test]
+fn span_basic_blank_narrow_to_content() {
+    const TEXT: &str = "ab\n   \ncd";
+    let source = SourceText::new(TEXT);
+    let span = Span::enclosing(
+            Pos::new(3, 1, 0),
+            Pos::new(6, 1, 3))
+        .narrow_to_content(source);
+
+    let actual = span;
+    let expected = Span::at(Pos::new(3, 1, 0));
+    assert_eq!(actual, expected);
+}
+
 /// Tests `Span::split_lines`.
 #[test]
 fn span_basic_split_lines() {
@@ -233,7 +315,7 @@ fn span_basic_union() {
     assert_eq!(actual, expected);
 }
 
-/// Tests `Span::intersect`.
+/// Tests `Span::intersection`.
 #[test]
 fn span_basic_intersect() {
     const TEXT: &str = "\n \n\n \nabcd\n def \nghi\n";
@@ -245,7 +327,7 @@ fn span_basic_intersect() {
     let b = Span::enclosing(
         Pos::new(5, 3, 1),
         Pos::new(20, 6, 3));
-    let span = a.intersect(b).unwrap();
+    let span = a.intersection(b).unwrap();
 
     // Check text clip.
     let actual = source.clipped(span);
@@ -258,6 +340,111 @@ fn span_basic_intersect() {
     assert_eq!(actual, expected);
 }
 
+/// Tests `Span::intersection` with adjacent spans across a line boundary.
+#[test]
+fn span_basic_intersection_adjacent() {
+    let a = Span::enclosing(
+        Pos::new(3, 2, 0),
+        Pos::new(10, 4, 4));
+    let b = Span::enclosing(
+        Pos::new(10, 4, 4),
+        Pos::new(17, 6, 0));
+
+    let actual = a.intersection(b).map(|span| format!("{span}"));
+    let expected = Some("4:4, byte 10".to_owned());
+    assert_eq!(actual, expected);
+
+    let actual = b.intersection(a).map(|span| format!("{span}"));
+    assert_eq!(actual, expected);
+}
+
+/// Tests `Span::intersection` with nested spans.
+#[test]
+fn span_basic_intersection_nested() {
+    let a = Span::enclosing(
+        Pos::new(3, 2, 0),
+        Pos::new(16, 5, 5));
+    let b = Span::enclosing(
+        Pos::new(6, 4, 0),
+        Pos::new(10, 4, 4));
+
+    assert_eq!(a.intersection(b), Some(b));
+    assert_eq!(b.intersection(a), Some(b));
+}
+
+/// Tests `Span::intersection` with disjoint spans.
+#[test]
+fn span_basic_intersection_disjoint() {
+    let a = Span::enclosing(
+        Pos::new(3, 2, 0),
+        Pos::new(10, 4, 4));
+    let b = Span::enclosing(
+        Pos::new(11, 5, 0),
+        Pos::new(20, 6, 3));
+
+    assert_eq!(a.intersection(b), None);
+    assert_eq!(b.intersection(a), None);
+}
+
+/// Tests `Span::contains`.
+#[test]
+fn span_basic_contains() {
+    let a = Span::enclosing(
+        Pos::new(3, 2, 0),
+        Pos::new(10, 4, 4));
+
+    assert!(a.contains(Pos::new(3, 2, 0)));
+    assert!(a.contains(Pos::new(6, 4, 0)));
+    assert!(a.contains(Pos::new(9, 4, 3)));
+    assert!(!a.contains(Pos::new(10, 4, 4)));
+    assert!(!a.contains(Pos::new(11, 5, 0)));
+    assert!(!a.contains(Pos::new(2, 1, 1)));
+
+    let empty = Span::at(Pos::new(6, 4, 0));
+    assert!(!empty.contains(Pos::new(6, 4, 0)));
+}
+
+/// Tests `Span::contains_span`.
+#[test]
+fn span_basic_contains_span() {
+    let a = Span::enclosing(
+        Pos::new(3, 2, 0),
+        Pos::new(10, 4, 4));
+    let nested = Span::enclosing(
+        Pos::new(6, 4, 0),
+        Pos::new(10, 4, 4));
+    let overlapping = Span::enclosing(
+        Pos::new(5, 3, 1),
+        Pos::new(16, 5, 5));
+    let disjoint = Span::enclosing(
+        Pos::new(11, 5, 0),
+        Pos::new(20, 6, 3));
+
+    assert!(a.contains_span(a));
+    assert!(a.contains_span(nested));
+    assert!(!nested.contains_span(a));
+    assert!(!a.contains_span(overlapping));
+    assert!(!a.contains_span(disjoint));
+    assert!(a.contains_span(Span::at(Pos::new(10, 4, 4))));
+}
+
+/// Tests `SourceText::display_position`.
+#[test]
+fn source_text_basic_display_position() {
+    const TEXT: &str = "abc\ndef\n";
+    let source = SourceText::new(TEXT);
+
+    assert_eq!(
+        source.display_position(Pos::new(8, 2, 0)),
+        Pos::new(7, 1, 3));
+    assert_eq!(
+        source.display_position(Pos::new(4, 1, 0)),
+        Pos::new(4, 1, 0));
+    assert_eq!(
+        source.display_position(Pos::new(7, 1, 3)),
+        Pos::new(7, 1, 3));
+}
+
 /// Tests `Span::minus`.
 #[test]
 fn span_basic_minus() {
@@ -301,3 +488,132 @@ fn source_text_lf_iter_columns() {
     ];
     assert_eq!(actual, expected);
 }
+
+/// Tests `Span::display_bytes`.
+#[test]
+fn span_basic_display_bytes() {
+    let span = Span::enclosing(
+        Pos::new(4, 1, 2),
+        Pos::new(8, 1, 6));
+
+    let actual = format!("{}", span.display_bytes());
+    let expected = "bytes 4-8";
+    assert_eq!(actual, expected);
+
+    let actual = format!("{}", Span::at(Pos::new(6, 1, 4)).display_bytes());
+    let expected = "byte 6";
+    assert_eq!(actual, expected);
+}
+
+/// Tests `Span::relative_to`.
+#[test]
+fn span_basic_relative_to() {
+    let span = Span::enclosing(
+        Pos::new(6, 1, 4),
+        Pos::new(14, 2, 3));
+
+    let actual = span.relative_to(Pos::new(4, 1, 2));
+    let expected = (Pos::new(2, 0, 2), Pos::new(10, 1, 3));
+    assert_eq!(actual, expected);
+
+    let actual = span.relative_to(Pos::new(10, 2, 0));
+    let expected = (Pos::new(0, 0, 0), Pos::new(4, 0, 3));
+    assert_eq!(actual, expected);
+}
+
+/// Tests `SourceText::pos_at`.
+#[test]
+fn source_text_basic_pos_at() {
+    const TEXT: &str = " \n  abcd  \n ";
+    let source = SourceText::new(TEXT);
+
+    let actual = source.pos_at(1, 4);
+    let expected = Some(Pos::new(6, 1, 4));
+    assert_eq!(actual, expected);
+
+    // Columns past the end of the line are clamped.
+    let actual = source.pos_at(1, 20);
+    let expected = Some(Pos::new(10, 1, 8));
+    assert_eq!(actual, expected);
+
+    let actual = source.pos_at(3, 0);
+    let expected = None;
+    assert_eq!(actual, expected);
+}
+
+/// Tests `SourceText::line_at`.
+#[test]
+fn source_text_basic_line_at() {
+    const TEXT: &str = "ab\ncd\n";
+    let source = SourceText::new(TEXT);
+
+    let actual = source.line_at(1);
+    let expected = Some(Span::enclosing(
+        Pos::new(3, 1, 0),
+        Pos::new(5, 1, 2)));
+    assert_eq!(actual, expected);
+
+    // A trailing line break is followed by an empty line.
+    let actual = source.line_at(2);
+    let expected = Some(Span::at(Pos::new(6, 2, 0)));
+    assert_eq!(actual, expected);
+
+    let actual = source.line_at(3);
+    let expected = None;
+    assert_eq!(actual, expected);
+}
+
+/// Tests `SourceText::lines`.
+#[test]
+fn source_text_basic_lines() {
+    const TEXT: &str = "ab\ncd\n";
+    let source = SourceText::new(TEXT);
+
+    let actual = source
+        .lines()
+        .map(|sp| format!("{:?} ({})", source.clipped(sp).as_ref(), sp))
+        .collect::<Vec<_>>();
+    let expected = vec![
+        "\"ab\" (0:0-0:2, bytes 0-2)".to_owned(),
+        "\"cd\" (1:0-1:2, bytes 3-5)".to_owned(),
+        "\"\" (2:0, byte 6)".to_owned(),
+    ];
+
+    assert_eq!(actual, expected);
+}
+
+/// Tests `SourceText::apply_edit`.
+#[test]
+fn source_text_basic_apply_edit() {
+    const TEXT: &str = "ab\ncd ef\ngh";
+    let source = SourceText::new(TEXT);
+
+    let (edited, remap) = source.apply_edit(3..5, "xyz\nw");
+    assert_eq!(edited.as_str(), "ab\nxyz\nw ef\ngh");
+
+    // Spans before the edit are unchanged.
+    let actual = remap(Span::enclosing(Pos::new(0, 0, 0), Pos::new(2, 0, 2)));
+    let expected = Some(Span::enclosing(
+        Pos::new(0, 0, 0),
+        Pos::new(2, 0, 2)));
+    assert_eq!(actual, expected);
+
+    // Spans on the edited line are shifted and re-measured.
+    let actual = remap(Span::enclosing(Pos::new(6, 1, 3), Pos::new(8, 1, 5)));
+    let expected = Some(Span::enclosing(
+        Pos::new(9, 2, 2),
+        Pos::new(11, 2, 4)));
+    assert_eq!(actual, expected);
+
+    // Spans on later lines are shifted.
+    let actual = remap(Span::enclosing(Pos::new(9, 2, 0), Pos::new(11, 2, 2)));
+    let expected = Some(Span::enclosing(
+        Pos::new(12, 3, 0),
+        Pos::new(14, 3, 2)));
+    assert_eq!(actual, expected);
+
+    // Spans overlapping the edit are invalidated.
+    let actual = remap(Span::enclosing(Pos::new(4, 1, 1), Pos::new(7, 1, 4)));
+    let expected = None;
+    assert_eq!(actual, expected);
+}
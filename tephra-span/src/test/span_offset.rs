@@ -257,7 +257,7 @@ fn span_offset_union() {
     assert_eq!(actual, expected);
 }
 
-/// Tests `Span::intersect`.
+/// Tests `Span::intersection`.
 #[test]
 fn span_offset_intersect() {
     const TEXT: &str = "\n \n\n \nabcd\n def \nghi\n";
@@ -270,7 +270,7 @@ fn span_offset_intersect() {
     let b = Span::enclosing(
         Pos::new(105, 13, 1),
         Pos::new(120, 16, 3));
-    let span = a.intersect(b).unwrap();
+    let span = a.intersection(b).unwrap();
 
     // Check text clip.
     let actual = source.clipped(span);
@@ -310,6 +310,72 @@ fn span_offset_minus() {
 }
 
 
+/// Tests `Span::translated_by` for a single-line span and multi-line delta.
+#[test]
+fn span_offset_translated_by() {
+    const TEXT: &str = "line one\nline\n  xabcdx";
+    let source = SourceText::new(TEXT);
+
+    let span = Span::enclosing(
+        Pos::new(1, 0, 1),
+        Pos::new(4, 0, 4));
+    let span = span.translated_by(Pos::new(16, 2, 2));
+
+    // Check text clip.
+    let actual = source.clipped(span);
+    let expected = "abc";
+    assert_eq!(actual.as_ref(), expected);
+
+    // Check span display.
+    let actual = format!("{span}");
+    let expected = "2:3-2:6, bytes 17-20";
+    assert_eq!(actual, expected);
+}
+
+/// Tests `Span::translated_by` for a multi-line span and multi-line delta.
+#[test]
+fn span_offset_translated_by_multiline() {
+    const TEXT: &str = "line one\nline\n  xab\ncd";
+    let source = SourceText::new(TEXT);
+
+    let span = Span::enclosing(
+        Pos::new(1, 0, 1),
+        Pos::new(6, 1, 2));
+    let span = span.translated_by(Pos::new(16, 2, 2));
+
+    // Check text clip.
+    let actual = source.clipped(span);
+    let expected = "ab\ncd";
+    assert_eq!(actual.as_ref(), expected);
+
+    // Check span display.
+    let actual = format!("{span}");
+    let expected = "2:3-3:2, bytes 17-22";
+    assert_eq!(actual, expected);
+}
+
+/// Tests `Span::reanchored`.
+#[test]
+fn span_offset_reanchored() {
+    const TEXT: &str = "line one\nline\n  xab\ncd";
+    let source = SourceText::new(TEXT);
+
+    let span = Span::enclosing(
+        Pos::new(1, 0, 1),
+        Pos::new(6, 1, 2));
+    let span = span.reanchored(Pos::new(17, 2, 3));
+
+    // Check text clip.
+    let actual = source.clipped(span);
+    let expected = "ab\ncd";
+    assert_eq!(actual.as_ref(), expected);
+
+    // Check span display.
+    let actual = format!("{span}");
+    let expected = "2:3-3:2, bytes 17-22";
+    assert_eq!(actual, expected);
+}
+
 /// Tests `SourceText::iter_columns` for `Lf`.
 #[test]
 fn source_text_lf_iter_columns() {
@@ -12,16 +12,28 @@
 #![allow(missing_docs)]
 
 // Internal library imports.
+use crate::Checkpoint;
 use crate::Scanner;
 use crate::Lexer;
+use crate::ParseResult;
+use crate::Success;
 
 // External library imports.
+use tephra_error::error::ErrorLimitError;
+use tephra_error::error::NotedError;
+use tephra_error::error::WarningError;
+use tephra_error::error::SourceErrorRef;
+use tephra_error::Note;
 use tephra_error::ParseError;
+use tephra_span::Pos;
 use tephra_span::Span;
 use tephra_tracing::event;
 use tephra_tracing::Level;
 
 // Standard library imports.
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::RwLock;
 
@@ -36,6 +48,12 @@ fn option_fmt<T>(opt: &Option<T>) -> &'static str {
 /// A function which can receive recoverable `ParseError`s.
 pub type ErrorSink<'text> = Box<dyn Fn(Box<dyn ParseError>) + 'text>;
 
+////////////////////////////////////////////////////////////////////////////////
+// WarningSink
+////////////////////////////////////////////////////////////////////////////////
+/// A function which can receive `ParseError`s reported as warnings.
+pub type WarningSink<'text> = Box<dyn Fn(Box<dyn ParseError>) + 'text>;
+
 ////////////////////////////////////////////////////////////////////////////////
 // ErrorTransform
 ////////////////////////////////////////////////////////////////////////////////
@@ -44,6 +62,100 @@ pub type ErrorTransform<'text> = Rc<
     dyn for<'a> Fn(Box<dyn ParseError>) -> Box<dyn ParseError> + 'text>;
 
 
+////////////////////////////////////////////////////////////////////////////////
+// ErrorScope
+////////////////////////////////////////////////////////////////////////////////
+/// A group of `ParseError`s collected by a child scope of a `Context`.
+///
+/// See [`Context::child_scope`].
+#[derive(Debug, Clone, Default)]
+pub struct ErrorScope {
+    /// The collected errors, in the order they were sent.
+    errors: Rc<RwLock<Vec<Box<dyn ParseError>>>>,
+}
+
+impl ErrorScope {
+    /// Returns the number of errors collected by the scope.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.errors.read().expect("lock error scope").len()
+    }
+
+    /// Returns `true` if no errors were collected by the scope.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.errors.read().expect("lock error scope").is_empty()
+    }
+
+    /// Removes and returns the errors collected by the scope, in the order
+    /// they were sent.
+    #[must_use]
+    pub fn take_errors(&self) -> Vec<Box<dyn ParseError>> {
+        std::mem::take(&mut *self.errors.write().expect("lock error scope"))
+    }
+
+    /// Removes the errors collected by the scope and sends them to the given
+    /// `Context`. `ErrorTransform`s are not reapplied to the errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors if the `Context` has no `ErrorSink` or error scope
+    /// to receive them.
+    pub fn forward_to<Sc>(&self, ctx: &Context<'_, Sc>)
+        -> Result<(), Vec<Box<dyn ParseError>>>
+        where Sc: Scanner
+    {
+        let errors = self.take_errors();
+        if ctx.suppressed {
+            return Ok(());
+        }
+        if let Some(scope) = ctx.scope.as_ref() {
+            scope.errors
+                .write()
+                .expect("lock error scope")
+                .extend(errors);
+            return Ok(());
+        }
+
+        let shared = ctx.shared.read().expect("lock shared context");
+        match shared.error_sink.as_ref() {
+            Some(sink) => {
+                event!(Level::DEBUG, "scoped errors sent to sink");
+                for e in errors {
+                    (sink)(e);
+                }
+                Ok(())
+            },
+            None => Err(errors),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ProgressSink
+////////////////////////////////////////////////////////////////////////////////
+/// A function which receives the lexer position as a parse progresses.
+pub type ProgressSink<'text> = Box<dyn Fn(Pos) + 'text>;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// CommitPolicy
+////////////////////////////////////////////////////////////////////////////////
+/// The handling of failures committed by an `unrecoverable` parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CommitPolicy {
+    /// A committed failure aborts error collection: the failure is made
+    /// unrecoverable, so it is propagated through any enclosing recovery to
+    /// the caller.
+    #[default]
+    Abort,
+    /// A committed failure is not recovered within the committed parse, but
+    /// enclosing recovery may collect the failure and continue.
+    Collect,
+}
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // SharedContext
 ////////////////////////////////////////////////////////////////////////////////
@@ -51,12 +163,53 @@ pub type ErrorTransform<'text> = Rc<
 struct SharedContext<'text> {
     /// The `ErrorSink` function.
     error_sink: Option<ErrorSink<'text>>,
+    /// The `WarningSink` function.
+    warning_sink: Option<WarningSink<'text>>,
+    /// The `ProgressSink` function.
+    progress_sink: Option<ProgressSink<'text>>,
+    /// The number of tokens to consume between progress reports.
+    progress_interval: usize,
+    /// The number of tokens consumed since the last progress report.
+    progress_count: usize,
+    /// The furthest position consumed by the parse.
+    progress_furthest: Option<Pos>,
+    /// The handling of committed failures.
+    commit_policy: CommitPolicy,
+    /// The maximum number of errors to send to the `ErrorSink`.
+    max_total_errors: Option<usize>,
+    /// The number of errors sent to the `ErrorSink`.
+    total_errors: usize,
+}
+
+impl<'text> SharedContext<'text> {
+    /// Constructs a new `SharedContext` with the given `ErrorSink`.
+    fn new(error_sink: Option<ErrorSink<'text>>) -> Self {
+        SharedContext {
+            error_sink,
+            warning_sink: None,
+            progress_sink: None,
+            progress_interval: 1,
+            progress_count: 0,
+            progress_furthest: None,
+            commit_policy: CommitPolicy::default(),
+            max_total_errors: None,
+            total_errors: 0,
+        }
+    }
 }
 
 impl<'text> std::fmt::Debug for SharedContext<'text> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SharedContext")
             .field("error_sink", &option_fmt(&self.error_sink))
+            .field("warning_sink", &option_fmt(&self.warning_sink))
+            .field("progress_sink", &option_fmt(&self.progress_sink))
+            .field("progress_interval", &self.progress_interval)
+            .field("progress_count", &self.progress_count)
+            .field("progress_furthest", &self.progress_furthest)
+            .field("commit_policy", &self.commit_policy)
+            .field("max_total_errors", &self.max_total_errors)
+            .field("total_errors", &self.total_errors)
             .finish()
     }
 }
@@ -119,6 +272,83 @@ impl<'text, Sc> std::fmt::Debug for LocalContext<'text, Sc> where Sc: Scanner {
 }
 
 
+////////////////////////////////////////////////////////////////////////////////
+// MemoTable
+////////////////////////////////////////////////////////////////////////////////
+/// A bounded table of memoized parse results for a single memoization key,
+/// with least-recently-used eviction.
+struct MemoTable<Sc> where Sc: Scanner {
+    /// The memoized results keyed by the start byte of the parse, as the
+    /// lexer checkpoint following the parse, the type-erased value, and the
+    /// generation in which the result was last used.
+    entries: HashMap<usize, (Checkpoint<Sc>, Rc<dyn Any>, u64)>,
+    /// The start bytes of the memoized results, ordered by the generation in
+    /// which they were last used.
+    order: BTreeMap<u64, usize>,
+    /// The most recent generation.
+    generation: u64,
+}
+
+impl<Sc> MemoTable<Sc> where Sc: Scanner {
+    /// Constructs a new empty `MemoTable`.
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            generation: 0,
+        }
+    }
+
+    /// Inserts a memoized result for the given start byte, marking it as the
+    /// most recently used.
+    fn insert(
+        &mut self,
+        byte: usize,
+        checkpoint: Checkpoint<Sc>,
+        value: Rc<dyn Any>)
+    {
+        if let Some((_, _, last_used)) = self.entries
+            .insert(byte, (checkpoint, value, 0))
+        {
+            let _ = self.order.remove(&last_used);
+        }
+        self.touch(byte);
+    }
+
+    /// Marks the result for the given start byte as the most recently used.
+    fn touch(&mut self, byte: usize) {
+        if let Some((_, _, last_used)) = self.entries.get_mut(&byte) {
+            let _ = self.order.remove(last_used);
+            self.generation += 1;
+            *last_used = self.generation;
+            let _ = self.order.insert(self.generation, byte);
+        }
+    }
+
+    /// Evicts the least recently used results until no more than `capacity`
+    /// remain, returning the start bytes of the evicted results.
+    fn evict_to(&mut self, capacity: usize) -> Vec<usize> {
+        let mut evicted = Vec::new();
+        while self.order.len() > capacity {
+            if let Some((_, byte)) = self.order.pop_first() {
+                let _ = self.entries.remove(&byte);
+                evicted.push(byte);
+            }
+        }
+        evicted
+    }
+}
+
+impl<Sc> std::fmt::Debug for MemoTable<Sc> where Sc: Scanner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoTable")
+            .field("order", &self.order)
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // Context
 ////////////////////////////////////////////////////////////////////////////////
@@ -129,8 +359,14 @@ pub struct Context<'text, Sc> where Sc: Scanner {
     shared: Rc<RwLock<SharedContext<'text>>>,
     /// The `LocalContext`.
     local: Rc<RwLock<LocalContext<'text, Sc>>>,
+    /// The `MemoTable`s for memoized parse results, by memoization key.
+    memo: Rc<RwLock<HashMap<&'static str, MemoTable<Sc>>>>,
     /// Indicates that the context is locked and no new contexts may be added.
     locked: bool,
+    /// Indicates that sent errors should be discarded.
+    suppressed: bool,
+    /// The `ErrorScope` collecting sent errors in place of the `ErrorSink`.
+    scope: Option<ErrorScope>,
 }
 
 impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
@@ -138,14 +374,15 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
     #[must_use]
     pub fn empty() -> Self {
         Context {
-            shared: Rc::new(RwLock::new(SharedContext {
-                error_sink: None,
-            })),
+            shared: Rc::new(RwLock::new(SharedContext::new(None))),
             local: Rc::new(RwLock::new(LocalContext {
                 error_transform: None,
                 parent: None,
             })),
+            memo: Rc::new(RwLock::new(HashMap::new())),
             locked: false,
+            suppressed: false,
+            scope: None,
         }
     }
 
@@ -153,15 +390,89 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
     #[must_use]
     pub fn new(error_sink: Option<ErrorSink<'text>>) -> Self {
         Context {
-            shared: Rc::new(RwLock::new(SharedContext {
-                error_sink,
-            })),
+            shared: Rc::new(RwLock::new(SharedContext::new(error_sink))),
             local: Rc::new(RwLock::new(LocalContext {
                 error_transform: None,
                 parent: None,
             })),
+            memo: Rc::new(RwLock::new(HashMap::new())),
             locked: false,
+            suppressed: false,
+            scope: None,
+        }
+    }
+
+    /// Returns the given `Context` with the given `ProgressSink`. The sink will
+    /// be called with the lexer position after every `interval` tokens are
+    /// consumed by the parse.
+    ///
+    /// Progress is measured by the furthest position consumed, so reported
+    /// positions never go backward, and tokens consumed again after a parse
+    /// backtracks are not counted again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    #[must_use]
+    pub fn with_progress_sink(
+        self,
+        progress_sink: ProgressSink<'text>,
+        interval: usize)
+        -> Self
+    {
+        assert!(interval > 0, "progress interval must be nonzero");
+        {
+            let mut shared = self.shared.write().expect("lock shared context");
+            shared.progress_sink = Some(progress_sink);
+            shared.progress_interval = interval;
+            shared.progress_count = 0;
+            shared.progress_furthest = None;
         }
+        self
+    }
+
+    /// Returns the given `Context` with the given `WarningSink`. Warnings are
+    /// collected separately from errors, and do not cause the parse to fail.
+    #[must_use]
+    pub fn with_warning_sink(self, warning_sink: WarningSink<'text>) -> Self {
+        self.shared
+            .write()
+            .expect("lock shared context")
+            .warning_sink = Some(warning_sink);
+        self
+    }
+
+    /// Returns the given `Context` with the given `CommitPolicy`. The policy
+    /// is shared by all `Context`s of the parse.
+    #[must_use]
+    pub fn with_commit_policy(self, commit_policy: CommitPolicy) -> Self {
+        self.shared
+            .write()
+            .expect("lock shared context")
+            .commit_policy = commit_policy;
+        self
+    }
+
+    /// Returns the given `Context` with the given maximum number of errors.
+    /// Once `max_total_errors` errors have been sent to the `ErrorSink`, any
+    /// further error is replaced by an unrecoverable `ErrorLimitError`, which
+    /// aborts the parse. The limit is shared by all `Context`s of the parse.
+    #[must_use]
+    pub fn with_max_total_errors(self, max_total_errors: usize) -> Self {
+        self.shared
+            .write()
+            .expect("lock shared context")
+            .max_total_errors = Some(max_total_errors);
+        self
+    }
+
+    /// Returns the `CommitPolicy` of the parse.
+    #[must_use]
+    pub fn commit_policy(&self) -> CommitPolicy {
+        self.shared
+            .read()
+            .expect("lock shared context")
+            .commit_policy
     }
 
     /// Sets the lock value of the `Context`. The value indicates that the
@@ -173,6 +484,38 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
     }
 
 
+    /// Calls the given function with a copy of the `Context` which discards all
+    /// errors sent to it. This is intended for speculative parses, whose
+    /// recoverable errors should not be emitted.
+    ///
+    /// Errors are only discarded if an `ErrorSink` or `ErrorScope` is
+    /// configured, so a `Context` without either still disables error
+    /// recovery.
+    pub fn suppressed<F, T>(&self, f: F) -> T
+        where F: FnOnce(Self) -> T
+    {
+        let mut ctx = self.clone();
+        ctx.suppressed = true;
+        (f)(ctx)
+    }
+
+    /// Returns a copy of the `Context` whose sent errors are collected into a
+    /// new `ErrorScope` instead of the `ErrorSink`, along with the scope. This
+    /// allows the errors of independent parses to be grouped for reporting.
+    ///
+    /// `ErrorTransform`s are applied to the errors before they are collected,
+    /// and the errors count toward the maximum number of errors. The collected
+    /// errors may be sent on to the parent `Context` with
+    /// [`ErrorScope::forward_to`].
+    #[must_use]
+    pub fn child_scope(&self) -> (Self, ErrorScope) {
+        event!(Level::DEBUG, "opened child error scope");
+        let scope = ErrorScope::default();
+        let mut ctx = self.clone();
+        ctx.scope = Some(scope.clone());
+        (ctx, scope)
+    }
+
     /// Constructs a new `Context` by wrapping a new `ErrorTransform` around the
     /// given `Context`.
     #[allow(clippy::if_not_else)]
@@ -186,7 +529,10 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
                     parent: Some(Rc::clone(&self.local)),
                     error_transform: Some(error_transform),
                 })),
+                memo: Rc::clone(&self.memo),
                 locked: false,
+                suppressed: self.suppressed,
+                scope: self.scope.clone(),
             }
         } else {
             self
@@ -205,6 +551,18 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
         }
     }
 
+    /// Attaches the given note to all `ParseError`s sent or applied through
+    /// the `Context`. The note is scoped like an `ErrorTransform`, so it does
+    /// not apply to errors raised by parent contexts.
+    pub fn push_note<N>(&mut self, note: N)
+        where N: Into<Note>
+    {
+        let note = note.into();
+        self.push(Rc::new(move |parse_error| {
+            Box::new(NotedError::new(parse_error, note.clone()))
+        }));
+    }
+
     /// Removes the `ErrorSink` from the `Context` if present.
     pub fn take_error_sink(&mut self) -> Option<ErrorSink<'text>> {
         let mut shared = self.shared.write().expect("lock shared context");
@@ -239,14 +597,55 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
             .apply_error_transform(parse_error)
     }
 
-    /// Sends a `ParseError` to the `ErrorSink`, applying `ErrorTransform`s.
+    /// Sends a `ParseError` to the `ErrorSink`, applying `ErrorTransform`s. If
+    /// the `Context` is a child scope, the error is collected by its
+    /// `ErrorScope` instead.
     ///
-    /// Returns the given error if no `ErrorSink` is configured.
+    /// Returns the given error if no `ErrorSink` or `ErrorScope` is
+    /// configured. Otherwise, if the `Context` is suppressed, the error is
+    /// discarded. If the maximum number of errors has been reached, an
+    /// `ErrorLimitError` is returned instead.
     pub fn send_error(
         &self,
         parse_error: Box<dyn ParseError>)
         -> Result<(), Box<dyn ParseError>>
     {
+        if self.suppressed {
+            if self.scope.is_none() && self.shared
+                .read()
+                .expect("lock shared context")
+                .error_sink
+                .is_none()
+            {
+                return Err(parse_error);
+            }
+            event!(Level::DEBUG, "error suppressed");
+            return Ok(());
+        }
+
+        {
+            let mut shared = self.shared.write().expect("lock shared context");
+            if shared.error_sink.is_some() || self.scope.is_some() {
+                if let Some(limit) = shared.max_total_errors {
+                    if shared.total_errors >= limit {
+                        event!(Level::DEBUG, "error limit exceeded");
+                        return Err(Box::new(ErrorLimitError {
+                            error_span: parse_error.error_span(),
+                            limit,
+                        }));
+                    }
+                }
+                shared.total_errors += 1;
+            }
+        }
+
+        if let Some(scope) = self.scope.as_ref() {
+            event!(Level::DEBUG, "error sent to scope");
+            let e = self.apply_error_transform_recursive(parse_error);
+            scope.errors.write().expect("lock error scope").push(e);
+            return Ok(());
+        }
+
         #[allow(clippy::significant_drop_in_scrutinee)]
         match self.shared
             .read()
@@ -263,6 +662,112 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
         }
     }
 
+    /// Sends a `ParseError` to the `WarningSink` as a warning, applying
+    /// `ErrorTransform`s. The warning is displayed with the warning
+    /// `MessageType`.
+    ///
+    /// The warning is discarded if no `WarningSink` is configured or the
+    /// `Context` is suppressed. Warnings do not count toward the maximum
+    /// number of errors.
+    pub fn push_warning(&self, parse_error: Box<dyn ParseError>) {
+        if self.suppressed {
+            event!(Level::DEBUG, "warning suppressed");
+            return;
+        }
+
+        let shared = self.shared.read().expect("lock shared context");
+        if let Some(sink) = shared.warning_sink.as_ref() {
+            event!(Level::DEBUG, "warning sent to sink");
+            (sink)(Box::new(WarningError::new(
+                self.apply_error_transform_recursive(parse_error))));
+        }
+    }
+
+    /// Removes the `ProgressSink` from the `Context` if present.
+    pub fn take_progress_sink(&mut self) -> Option<ProgressSink<'text>> {
+        let mut shared = self.shared.write().expect("lock shared context");
+        shared.progress_sink.take()
+    }
+
+    /// Records that a token was consumed by the parse, sending the given
+    /// position to the `ProgressSink` if the progress interval has been
+    /// reached.
+    ///
+    /// Positions which do not advance past the furthest position consumed are
+    /// ignored, so that tokens consumed again after a parse backtracks are not
+    /// counted again. This does nothing if no `ProgressSink` is configured.
+    pub fn report_progress(&self, pos: Pos) {
+        let mut shared = self.shared.write().expect("lock shared context");
+        if shared.progress_sink.is_none()
+            || shared.progress_furthest.is_some_and(|p| pos.byte <= p.byte)
+        {
+            return;
+        }
+        shared.progress_furthest = Some(pos);
+        shared.progress_count += 1;
+        if shared.progress_count < shared.progress_interval {
+            return;
+        }
+        shared.progress_count = 0;
+
+        // The sink is called without holding the lock, so that it may use the
+        // context.
+        let sink = shared.progress_sink.take();
+        drop(shared);
+        if let Some(sink) = sink {
+            event!(Level::TRACE, "progress reported ({})", pos);
+            (sink)(pos);
+            let mut shared = self.shared.write().expect("lock shared context");
+            if shared.progress_sink.is_none() {
+                shared.progress_sink = Some(sink);
+            }
+        }
+    }
+
+    /// Returns the memoized parse result for the given key and start byte, if
+    /// present, as the lexer checkpoint following the parse and the parsed
+    /// value.
+    pub fn memo_get<V>(&self, key: &'static str, byte: usize)
+        -> Option<(Checkpoint<Sc>, V)>
+        where V: Clone + 'static
+    {
+        let mut memo = self.memo.write().expect("lock memo table");
+        let table = memo.get_mut(key)?;
+        let (checkpoint, value, _) = table.entries.get(&byte)?;
+        let value = value.downcast_ref::<V>()?.clone();
+        let checkpoint = checkpoint.clone();
+        table.touch(byte);
+        drop(memo);
+        event!(Level::TRACE, "memo hit ({}@{})", key, byte);
+        Some((checkpoint, value))
+    }
+
+    /// Memoizes a parse result for the given key and start byte. If the number
+    /// of memoized results for the key exceeds `capacity`, the least recently
+    /// used results for the key are evicted.
+    pub fn memo_insert<V>(
+        &self,
+        capacity: usize,
+        key: &'static str,
+        byte: usize,
+        succ: &Success<'text, Sc, V>)
+        where V: Clone + 'static
+    {
+        let mut memo = self.memo.write().expect("lock memo table");
+        let table = memo.entry(key).or_insert_with(MemoTable::new);
+        table.insert(
+            byte,
+            succ.lexer.checkpoint(),
+            Rc::new(succ.value.clone()));
+        let evicted = table.evict_to(capacity);
+        drop(memo);
+
+        #[cfg_attr(not(feature="tracing"), allow(unused_variables))]
+        for evicted in evicted {
+            event!(Level::TRACE, "memo evicted ({}@{})", key, evicted);
+        }
+    }
+
     /// Removes the `LocalContext` from the `Context` if present.
     pub fn take_local_context(&mut self) -> LocalContext<'text, Sc> {
         std::mem::replace(&mut *self.local
@@ -284,3 +789,140 @@ impl<'text, Sc> Context<'text, Sc> where Sc: Scanner {
     }
 }
 
+
+////////////////////////////////////////////////////////////////////////////////
+// Parse drivers
+////////////////////////////////////////////////////////////////////////////////
+/// Runs the given parser on the given lexer, collecting all emitted errors.
+///
+/// Returns the parsed value, or `None` if the parse failed, along with all
+/// recovered errors and any final parse error, in the order they occurred.
+pub fn parse_collecting<'text, Sc, F, V>(lexer: Lexer<'text, Sc>, mut parser: F)
+    -> (Option<V>, Vec<SourceErrorRef<'text>>)
+    where
+        Sc: Scanner,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, V>,
+{
+    let source_text = lexer.source_text();
+    let errors = Rc::new(RwLock::new(Vec::new()));
+    let ctx_errors = errors.clone();
+    let ctx = Context::new(Some(Box::new(move |e|
+        ctx_errors
+            .write()
+            .expect("lock error sink")
+            .push(e.into_source_error(source_text))
+    )));
+
+    let value = match (parser)(lexer, ctx) {
+        Ok(succ)  => Some(succ.value),
+        Err(fail) => {
+            errors
+                .write()
+                .expect("lock error sink")
+                .push(fail.into_source_error(source_text));
+            None
+        },
+    };
+
+    let errors = std::mem::take(&mut *errors
+        .write()
+        .expect("lock error sink"));
+    (value, errors)
+}
+
+/// Runs the given parser on the given lexer, continuing past recovered errors.
+///
+/// Returns the parsed value if no errors were emitted. Otherwise returns a
+/// [`ParseFailure`] holding every recovered error and any final parse error.
+pub fn parse_continuing<'text, Sc, F, V>(lexer: Lexer<'text, Sc>, mut parser: F)
+    -> Result<V, ParseFailure<'text>>
+    where
+        Sc: Scanner,
+        F: FnMut(Lexer<'text, Sc>, Context<'text, Sc>)
+            -> ParseResult<'text, Sc, V>,
+{
+    let source_text = lexer.source_text();
+    let errors = Rc::new(RwLock::new(Vec::new()));
+    let ctx_errors = errors.clone();
+    let ctx = Context::new(Some(Box::new(move |e| {
+        let start = e.error_span().map(|span| span.start().byte);
+        ctx_errors
+            .write()
+            .expect("lock error sink")
+            .push((start, e.into_source_error(source_text)));
+    })));
+
+    let value = match (parser)(lexer, ctx) {
+        Ok(succ)  => Some(succ.value),
+        Err(fail) => {
+            let start = fail.error_span().map(|span| span.start().byte);
+            errors
+                .write()
+                .expect("lock error sink")
+                .push((start, fail.into_source_error(source_text)));
+            None
+        },
+    };
+
+    let mut errors = std::mem::take(&mut *errors
+        .write()
+        .expect("lock error sink"));
+
+    match value {
+        Some(value) if errors.is_empty() => Ok(value),
+        _ => {
+            // Errors without a span are ordered after all spanned errors.
+            errors.sort_by_key(|(start, _)| start.unwrap_or(usize::MAX));
+            Err(ParseFailure {
+                errors: errors.into_iter().map(|(_, e)| e).collect(),
+            })
+        },
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// ParseFailure
+////////////////////////////////////////////////////////////////////////////////
+/// The errors collected by a continuing parse.
+#[derive(Debug)]
+pub struct ParseFailure<'text> {
+    /// The collected errors, in source order.
+    errors: Vec<SourceErrorRef<'text>>,
+}
+
+impl<'text> ParseFailure<'text> {
+    /// Returns an iterator over the collected errors in source order.
+    pub fn iter_errors(&self)
+        -> impl Iterator<Item=&SourceErrorRef<'text>> + '_
+    {
+        self.errors.iter()
+    }
+
+    /// Returns the number of collected errors.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no errors were collected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Consumes the failure, returning the collected errors in source order.
+    #[must_use]
+    pub fn into_errors(self) -> Vec<SourceErrorRef<'text>> {
+        self.errors
+    }
+}
+
+impl std::fmt::Display for ParseFailure<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for error in &self.errors {
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
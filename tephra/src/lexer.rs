@@ -10,6 +10,7 @@
 #![allow(missing_docs)]
 
 // Internal library imports.
+use crate::Spanned;
 use tephra_error::Highlight;
 use tephra_error::CodeDisplay;
 use tephra_error::SpanDisplay;
@@ -22,8 +23,15 @@ use tephra_span::SourceTextRef;
 use tephra_error::Recover;
 
 // Standard library imports.
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::ops::Deref;
+use std::ops::DerefMut;
 use std::rc::Rc;
 
 
@@ -38,6 +46,68 @@ pub trait Scanner: Debug + Clone + PartialEq {
 
     fn scan(&mut self, source: SourceTextRef<'_>, base: Pos)
         -> Option<(Self::Token, Pos)>;
+
+    /// Returns `true` if scanning can be safely restarted at the given
+    /// position with the scanner in its current state. This should be `false`
+    /// for positions within a construct the scanner tracks across tokens, such
+    /// as an open string or comment.
+    ///
+    /// This is intended for use in finding a position from which to resume
+    /// scanning after an edit to the source text. The default implementation
+    /// always returns `true`, which is correct for stateless scanners.
+    fn is_safe_restart_point(&self, _source: SourceTextRef<'_>, _pos: Pos)
+        -> bool
+    {
+        true
+    }
+}
+
+/// Retained trivia tokens, keyed by the position of the token following them.
+pub type TriviaMap<T> = BTreeMap<Pos, Vec<Spanned<T>>>;
+
+/// Scans a single-character token from the given table of characters and
+/// tokens. Returns the token for the first matching character at the given
+/// base position, along with the position following it.
+///
+/// This is intended to simplify `Scanner` implementations with many
+/// single-character punctuation tokens.
+pub fn scan_char_table<T>(
+    source: SourceTextRef<'_>,
+    base: Pos,
+    table: &[(char, T)])
+    -> Option<(T, Pos)>
+    where T: Clone
+{
+    let byte = base.byte - source.start_position().byte;
+    let c = source.as_str()[byte..].chars().next()?;
+    let (_, token) = table.iter().find(|(tc, _)| *tc == c)?;
+    let adv = source.next_position(base)?;
+    Some((token.clone(), adv))
+}
+
+/// Scans a token using each of the given scan functions, returning the token
+/// which extends furthest from the given base position, along with the
+/// position following it. If multiple scan functions produce tokens of the
+/// same length, the first one is returned.
+///
+/// This is intended to simplify `Scanner` implementations whose tokens share
+/// prefixes, providing longest-match semantics without requiring the scan
+/// functions be carefully ordered.
+pub fn scan_longest_match<T>(
+    source: SourceTextRef<'_>,
+    base: Pos,
+    scanners: &[&dyn Fn(SourceTextRef<'_>, Pos) -> Option<(T, Pos)>])
+    -> Option<(T, Pos)>
+{
+    let mut longest: Option<(T, Pos)> = None;
+    for scan in scanners {
+        if let Some((token, adv)) = (scan)(source, base) {
+            if longest.as_ref().map_or(true, |(_, end)| adv.byte > end.byte) {
+                longest = Some((token, adv));
+            }
+        }
+    }
+    longest
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -50,21 +120,322 @@ struct ScannerBuffer<Sc>
     token: Sc::Token
 }
 
+/// A token scanned from a position with a given scanner state.
+#[derive(Debug, Clone)]
+struct CachedToken<Sc>
+    where Sc: Scanner,
+{
+    /// The scanner state preceding the scan.
+    scanner: Sc,
+    /// The scanner state following the scan.
+    next_scanner: Sc,
+    /// The scanned token.
+    token: Sc::Token,
+    /// The position following the token.
+    adv: Pos,
+}
+
+/// Tokens scanned by a `Lexer`, shared with its clones so that speculative
+/// parses do not scan the same text more than once. The cache is enabled with
+/// `Lexer::with_token_cache`.
+///
+/// Tokens are keyed by their start byte and the scanner state preceding the
+/// scan, so stateful and modal scanners only reuse tokens scanned in the same
+/// state.
+struct TokenCache<Sc>
+    where Sc: Scanner,
+{
+    /// The cached tokens, keyed by start byte.
+    entries: RefCell<HashMap<usize, Vec<CachedToken<Sc>>>>,
+}
+
+impl<Sc> TokenCache<Sc>
+    where Sc: Scanner,
+{
+    /// Constructs a new empty `TokenCache`.
+    fn new() -> Self {
+        Self { entries: RefCell::new(HashMap::new()) }
+    }
+
+    /// Scans the next token from the given base position, reusing a cached
+    /// token if one was scanned from the same position and scanner state. The
+    /// given scan count is incremented if the scanner is called.
+    fn scan(
+        &self,
+        scanner: &mut Sc,
+        source: SourceTextRef<'_>,
+        base: Pos,
+        scan_count: &Cell<usize>)
+        -> Option<(Sc::Token, Pos)>
+    {
+        if let Some(cached) = self.entries
+            .borrow()
+            .get(&base.byte)
+            .and_then(|e| e.iter().find(|c| c.scanner == *scanner))
+        {
+            *scanner = cached.next_scanner.clone();
+            return Some((cached.token.clone(), cached.adv));
+        }
+
+        let prior = scanner.clone();
+        scan_count.set(scan_count.get() + 1);
+        let (token, adv) = scanner.scan(source, base)?;
+        self.entries
+            .borrow_mut()
+            .entry(base.byte)
+            .or_default()
+            .push(CachedToken {
+                scanner: prior,
+                next_scanner: scanner.clone(),
+                token: token.clone(),
+                adv,
+            });
+        Some((token, adv))
+    }
+}
+
+/// Scans the next token from the given base position, using the given token
+/// cache if there is one. The given scan count is incremented if the scanner
+/// is called.
+fn scan_with_cache<Sc>(
+    cache: Option<&TokenCache<Sc>>,
+    scanner: &mut Sc,
+    source: SourceTextRef<'_>,
+    base: Pos,
+    scan_count: &Cell<usize>)
+    -> Option<(Sc::Token, Pos)>
+    where Sc: Scanner,
+{
+    if let Some(cache) = cache {
+        return cache.scan(scanner, source, base, scan_count);
+    }
+    scan_count.set(scan_count.get() + 1);
+    scanner.scan(source, base)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Checkpoint
+////////////////////////////////////////////////////////////////////////////////
+/// A saved lexer position and scanner state, which a lexer can be reset to
+/// with `Lexer::reset_to`.
+///
+/// The lexer also saves a `Checkpoint` before producing each token, so that
+/// it can be restored by `Lexer::unget`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint<Sc>
+    where Sc: Scanner,
+{
+    /// The scanner state at the cursor.
+    scanner: Sc,
+    /// The start position of the current parse span.
+    parse_start: Pos,
+    /// The start position of the current token span.
+    token_start: Pos,
+    /// The cursor position.
+    cursor: Pos,
+    /// The span of the most recently produced token.
+    last_span: Option<Span>,
+}
+
+impl<Sc> Checkpoint<Sc>
+    where Sc: Scanner,
+{
+    /// Returns the cursor position of the checkpoint.
+    #[must_use]
+    pub fn cursor_pos(&self) -> Pos {
+        self.cursor
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// ModalScanner
+////////////////////////////////////////////////////////////////////////////////
+/// Selects the active sub-scanner of a `ModalScanner`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScanMode {
+    /// The primary scanner is active.
+    Primary,
+    /// The secondary scanner is active.
+    Secondary,
+}
+
+/// A `Scanner` which combines two scanners producing the same token type.
+///
+/// Each token is scanned by the active sub-scanner. After each token, the
+/// mode selector is called with the token, and if it returns a `ScanMode`, the
+/// corresponding sub-scanner becomes active for the next token. This allows
+/// scanners for distinct lexical modes to be written separately.
+#[derive(Debug, Clone)]
+pub struct ModalScanner<A, B>
+    where
+        A: Scanner,
+        B: Scanner<Token = A::Token>,
+{
+    /// The scanner used in `ScanMode::Primary`.
+    primary: A,
+    /// The scanner used in `ScanMode::Secondary`.
+    secondary: B,
+    /// The active scan mode.
+    mode: ScanMode,
+    /// Returns the scan mode to switch to after the given token, if any.
+    select: fn(&A::Token) -> Option<ScanMode>,
+}
+
+impl<A, B> ModalScanner<A, B>
+    where
+        A: Scanner,
+        B: Scanner<Token = A::Token>,
+{
+    /// Constructs a new `ModalScanner` from the given sub-scanners and mode
+    /// selector. The primary scanner is initially active.
+    #[must_use]
+    pub fn new(
+        primary: A,
+        secondary: B,
+        select: fn(&A::Token) -> Option<ScanMode>)
+        -> Self
+    {
+        ModalScanner {
+            primary,
+            secondary,
+            mode: ScanMode::Primary,
+            select,
+        }
+    }
+
+    /// Returns the given `ModalScanner` with the given initial scan mode.
+    #[must_use]
+    pub fn with_mode(mut self, mode: ScanMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the active scan mode.
+    #[must_use]
+    pub fn mode(&self) -> ScanMode {
+        self.mode
+    }
+}
+
+impl<A, B> PartialEq for ModalScanner<A, B>
+    where
+        A: Scanner,
+        B: Scanner<Token = A::Token>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        // The mode selector is not compared, as function pointer comparisons
+        // are unreliable.
+        self.primary == other.primary
+            && self.secondary == other.secondary
+            && self.mode == other.mode
+    }
+}
+
+impl<A, B> Scanner for ModalScanner<A, B>
+    where
+        A: Scanner,
+        B: Scanner<Token = A::Token>,
+{
+    type Token = A::Token;
+
+    fn scan(&mut self, source: SourceTextRef<'_>, base: Pos)
+        -> Option<(Self::Token, Pos)>
+    {
+        let (token, adv) = match self.mode {
+            ScanMode::Primary   => self.primary.scan(source, base)?,
+            ScanMode::Secondary => self.secondary.scan(source, base)?,
+        };
+
+        if let Some(mode) = (self.select)(&token) {
+            self.mode = mode;
+        }
+        Some((token, adv))
+    }
+
+    fn is_safe_restart_point(&self, source: SourceTextRef<'_>, pos: Pos)
+        -> bool
+    {
+        match self.mode {
+            ScanMode::Primary   => self.primary
+                .is_safe_restart_point(source, pos),
+            ScanMode::Secondary => self.secondary
+                .is_safe_restart_point(source, pos),
+        }
+    }
+}
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // Lexer
 ////////////////////////////////////////////////////////////////////////////////
+/// Lexer settings which are rarely used, boxed to reduce the size of the
+/// `Lexer`.
+#[derive(Clone)]
+struct LexerExtras<Sc>
+    where Sc: Scanner,
+{
+    /// The filters saved by `Lexer::push_filter`.
+    filter_stack: Vec<Option<Rc<dyn Fn(&Sc::Token) -> bool>>>,
+    /// The predicate selecting the filtered tokens to retain as trivia.
+    trivia: Option<Rc<dyn Fn(&Sc::Token) -> bool>>,
+    /// The token at which the lexer stops as if at the end of the text.
+    terminator: Option<Sc::Token>,
+}
+
+impl<Sc> LexerExtras<Sc>
+    where Sc: Scanner,
+{
+    /// Constructs a new `LexerExtras` with no settings.
+    fn new() -> Self {
+        Self {
+            filter_stack: Vec::new(),
+            trivia: None,
+            terminator: None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Lexer<'text, Sc>
     where Sc: Scanner,
 {
+    /// The source text being lexed.
     source_text: SourceTextRef<'text>,
+    /// The scanner state at the cursor.
     scanner: Sc,
+    /// The token filter. Tokens for which it returns `false` are skipped.
     filter: Option<Rc<dyn Fn(&Sc::Token) -> bool>>,
+    /// The rarely used lexer settings, allocated when first set.
+    extras: Option<Box<LexerExtras<Sc>>>,
+    /// The retained trivia tokens, shared with clones of the lexer.
+    trivia_buffer: Rc<RefCell<TriviaMap<Sc::Token>>>,
+    /// The error recovery state.
     recover: Option<Recover<Sc::Token>>,
+    /// The next non-filtered token, if it has been peeked.
     buffer: Option<ScannerBuffer<Sc>>,
+    /// The non-filtered tokens following the buffered token, scanned by
+    /// `peek_nth`.
+    // The lookahead is boxed, as it is rarely used and the lexer is cloned
+    // often.
+    #[allow(clippy::box_collection)]
+    lookahead: Option<Box<VecDeque<ScannerBuffer<Sc>>>>,
+    /// The lexer state preceding the most recently produced token, restored
+    /// by `unget`.
+    unget: Option<Box<Checkpoint<Sc>>>,
+    /// The span of the most recently produced token.
+    last_span: Option<Span>,
+    /// The number of scanner calls, shared with clones of the lexer.
+    scan_count: Rc<Cell<usize>>,
+    /// The scanned tokens, shared with clones of the lexer, if the token cache
+    /// is enabled.
+    token_cache: Option<Rc<TokenCache<Sc>>>,
+    /// The start position of the current parse span.
     parse_start: Pos,
+    /// The start position of the current token span.
     token_start: Pos,
+    /// The cursor position.
     cursor: Pos,
+    /// Whether filtered tokens are skipped before they are requested.
     filter_eager: bool,
 }
 
@@ -79,9 +450,16 @@ impl<'text, Sc> Lexer<'text, Sc>
             source_text,
             scanner,
             filter: None,
+            extras: None,
+            trivia_buffer: Rc::new(RefCell::new(BTreeMap::new())),
             filter_eager: true,
             recover: None,
             buffer: None,
+            lookahead: None,
+            unget: None,
+            last_span: None,
+            scan_count: Rc::new(Cell::new(0)),
+            token_cache: None,
             parse_start: Pos::default(),
             token_start: Pos::default(),
             cursor: Pos::default(),
@@ -117,6 +495,47 @@ impl<'text, Sc> Lexer<'text, Sc>
         self
     }
 
+    /// Returns the given `Lexer` with the given trivia predicate. Filtered
+    /// tokens satisfying the predicate are retained rather than discarded, and
+    /// can be retrieved with `take_trivia` or `trivia_before`.
+    #[must_use]
+    pub fn with_trivia(mut self, trivia: Option<Rc<dyn Fn(&Sc::Token) -> bool>>)
+        -> Self
+    {
+        self.extras_mut().trivia = trivia;
+        self.buffer = None;
+        self.lookahead = None;
+        if self.filter_eager {
+            self.buffer_next();
+        }
+        self
+    }
+
+    /// Returns the given `Lexer` with the token cache enabled or disabled.
+    ///
+    /// When enabled, scanned tokens are cached and shared with any clones of
+    /// the lexer, so that speculative parses do not scan the same text more
+    /// than once. Tokens are cached by position and scanner state, so this
+    /// trades memory for scanning time, and is most useful for grammars with
+    /// expensive scanners and frequent backtracking.
+    #[must_use]
+    pub fn with_token_cache(mut self, enabled: bool) -> Self {
+        self.token_cache = enabled.then(|| Rc::new(TokenCache::new()));
+        self
+    }
+
+    /// Returns the given `Lexer` with the given terminator token. The lexer
+    /// stops before the terminator as if it were the end of the text, so
+    /// `peek` and `next` return `None` and the terminator is not consumed.
+    ///
+    /// This is intended for parsing a sequence of terminated statements one
+    /// at a time. Use `set_terminator` to remove the terminator and consume it.
+    #[must_use]
+    pub fn with_terminator(mut self, terminator: Option<Sc::Token>) -> Self {
+        let _ = self.set_terminator(terminator);
+        self
+    }
+
     // Accessors
     ////////////////////////////////////////////////////////////////////////////
 
@@ -131,6 +550,10 @@ impl<'text, Sc> Lexer<'text, Sc>
     }
 
     fn column_metrics_mut(&mut self) -> &mut ColumnMetrics {
+        // Cached token positions depend on the column metrics.
+        if self.token_cache.is_some() {
+            self.token_cache = Some(Rc::new(TokenCache::new()));
+        }
         self.source_text.column_metrics_mut()
     }
 
@@ -166,6 +589,43 @@ impl<'text, Sc> Lexer<'text, Sc>
         self.filter.as_ref()
     }
 
+    /// Replaces the terminator token with the given token, returning the
+    /// previous terminator.
+    pub fn set_terminator(&mut self, terminator: Option<Sc::Token>)
+        -> Option<Sc::Token>
+    {
+        if terminator.is_none() && self.extras.is_none() {
+            return None;
+        }
+        std::mem::replace(&mut self.extras_mut().terminator, terminator)
+    }
+
+    /// Returns the terminator token.
+    fn terminator(&self) -> Option<&Sc::Token> {
+        self.extras.as_ref()?.terminator.as_ref()
+    }
+
+    /// Returns the rarely used lexer settings, allocating them if necessary.
+    fn extras_mut(&mut self) -> &mut LexerExtras<Sc> {
+        self.extras.get_or_insert_with(|| Box::new(LexerExtras::new()))
+    }
+
+    /// Returns `true` if the next non-filtered token is the terminator token.
+    pub fn is_at_terminator(&mut self) -> bool {
+        if self.terminator().is_none()
+            || self.cursor.byte >= self.source_text.len()
+        {
+            return false;
+        }
+        self.buffer_next();
+        self.buffer.as_ref().map(|buf| &buf.token) == self.terminator()
+    }
+
+    /// Replaces the token filter with the given filter, returning the previous
+    /// filter.
+    ///
+    /// Any peeked tokens are discarded, so the new filter applies to the very
+    /// next token produced by `peek`, `peek_nth`, or `next`.
     pub fn set_filter(
         &mut self,
         filter: Option<Rc<dyn Fn(&Sc::Token) -> bool>>)
@@ -174,12 +634,92 @@ impl<'text, Sc> Lexer<'text, Sc>
         let res = self.filter.take();
         self.filter = filter;
         self.buffer = None;
+        self.lookahead = None;
         if self.filter_eager {
             self.buffer_next();
         }
         res
     }
 
+    /// Replaces the token filter with the given filter, saving the current
+    /// filter so that it can be restored with `pop_filter`.
+    pub fn push_filter(
+        &mut self,
+        filter: Option<Rc<dyn Fn(&Sc::Token) -> bool>>)
+    {
+        let prev = self.set_filter(filter);
+        self.extras_mut().filter_stack.push(prev);
+    }
+
+    /// Restores the token filter saved by the most recent call to
+    /// `push_filter`, returning the removed filter. If there is no saved
+    /// filter, the current filter is retained and `None` is returned.
+    pub fn pop_filter(&mut self) -> Option<Rc<dyn Fn(&Sc::Token) -> bool>> {
+        let prev = self.extras.as_mut()?.filter_stack.pop()?;
+        self.set_filter(prev)
+    }
+
+    /// Replaces the token filter with the given filter, returning a guard
+    /// which restores the current filter when dropped. The lexer can be
+    /// accessed through the guard.
+    #[must_use]
+    pub fn with_filter_scope(
+        &mut self,
+        filter: Option<Rc<dyn Fn(&Sc::Token) -> bool>>)
+        -> FilterGuard<'_, 'text, Sc>
+    {
+        let depth = self.extras
+            .as_ref()
+            .map_or(0, |extras| extras.filter_stack.len());
+        self.push_filter(filter);
+        FilterGuard { lexer: self, depth }
+    }
+
+    // Trivia
+    ////////////////////////////////////////////////////////////////////////////
+
+    /// Removes and returns all retained trivia tokens, keyed by the position
+    /// of the token following them. The retained trivia is shared with any
+    /// clones of the lexer.
+    pub fn take_trivia(&self) -> TriviaMap<Sc::Token> {
+        std::mem::take(&mut *self.trivia_buffer.borrow_mut())
+    }
+
+    /// Returns the retained trivia tokens preceding the token at the given
+    /// position.
+    pub fn trivia_before(&self, pos: Pos) -> Vec<Spanned<Sc::Token>> {
+        self.trivia_buffer
+            .borrow()
+            .get(&pos)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Pushes the given filtered token into the pending trivia if it satisfies
+    /// the trivia predicate.
+    fn push_trivia(
+        &self,
+        pending: &mut Vec<Spanned<Sc::Token>>,
+        token: &Sc::Token,
+        span: Span)
+    {
+        let trivia = self.extras
+            .as_ref()
+            .and_then(|extras| extras.trivia.as_ref());
+        if trivia.map_or(false, |t| (t)(token)) {
+            pending.push(Spanned { value: token.clone(), span });
+        }
+    }
+
+    /// Stores the pending trivia, keyed by the position of the following
+    /// token.
+    fn store_trivia(&self, pending: Vec<Spanned<Sc::Token>>, next: Pos) {
+        if pending.is_empty() { return; }
+        let _ = self.trivia_buffer
+            .borrow_mut()
+            .insert(next, pending);
+    }
+
     // Spans
     ////////////////////////////////////////////////////////////////////////////
     pub fn start_sublex(&mut self) {
@@ -208,6 +748,44 @@ impl<'text, Sc> Lexer<'text, Sc>
         self.cursor
     }
 
+    /// Returns the source text from the cursor to the end of the text.
+    ///
+    /// This operates on the raw source text, so it includes the text of any
+    /// tokens which would be filtered.
+    #[must_use]
+    pub fn remaining_text(&self) -> &'text str {
+        let offset = self.source_text.start_position().byte;
+        let text: &'text str = self.source_text.text();
+        &text[self.cursor.byte - offset..]
+    }
+
+    /// Returns the source text from the start of the parse span to the cursor.
+    ///
+    /// This operates on the raw source text, so it includes the text of any
+    /// filtered tokens within the parse span.
+    #[must_use]
+    pub fn consumed_text(&self) -> &'text str {
+        let offset = self.source_text.start_position().byte;
+        let text: &'text str = self.source_text.text();
+        &text[self.parse_start.byte - offset..self.cursor.byte - offset]
+    }
+
+    /// Returns the span of the most recently produced (non-filtered) token, or
+    /// `None` if no token has been produced.
+    pub fn last_span(&self) -> Option<Span> {
+        self.last_span
+    }
+
+    /// Returns the total number of `Scanner::scan` calls made by the lexer,
+    /// including scans of filtered tokens and peeks. The count is shared with
+    /// any clones of the lexer.
+    ///
+    /// If the token cache is enabled, a token rescanned from the same position
+    /// and scanner state is not counted.
+    pub fn scan_count(&self) -> usize {
+        self.scan_count.get()
+    }
+
     pub fn peek_token_span(&self) -> Option<Span> {
         self.buffer
             .as_ref()
@@ -242,11 +820,20 @@ impl<'text, Sc> Lexer<'text, Sc>
         let behind = self.parse_start == self.cursor;
         let mut peek_scanner = self.scanner.clone();
         let mut peek_cursor = self.cursor;
-        while let Some((tok, adv)) = peek_scanner
-            .scan(self.source_text, peek_cursor)
+        let mut trivia = Vec::new();
+        while let Some((tok, adv)) = scan_with_cache(
+            self.token_cache.as_deref(),
+            &mut peek_scanner,
+            self.source_text,
+            peek_cursor,
+            &self.scan_count)
         {
             if self.filter.as_ref().map_or(false, |f| !(f)(&tok)) {
                 // Found a filtered token.
+                self.push_trivia(
+                    &mut trivia,
+                    &tok,
+                    Span::enclosing(peek_cursor, adv));
                 peek_cursor = adv;
                 if behind && self.filter_eager {
                     self.scanner = peek_scanner.clone();
@@ -265,10 +852,13 @@ impl<'text, Sc> Lexer<'text, Sc>
                 break;
             }
         }
+        self.store_trivia(trivia, peek_cursor);
     }
 
     pub fn peek(&mut self) -> Option<Sc::Token> {
-        if self.cursor.byte >= self.source_text.len() {
+        if self.cursor.byte >= self.source_text.len()
+            || self.is_at_terminator()
+        {
             return None;
         }
         self.buffer_next();
@@ -277,6 +867,69 @@ impl<'text, Sc> Lexer<'text, Sc>
             .map(|buf| buf.token.clone())
     }
 
+    /// Returns the token `n` tokens after the next token without advancing
+    /// the lexer, respecting the current filter. `peek_nth(0)` is equivalent
+    /// to `peek`.
+    ///
+    /// The scanned tokens are retained, so they will not be scanned again
+    /// when they are consumed. They are discarded if the filter is changed.
+    pub fn peek_nth(&mut self, n: usize) -> Option<Sc::Token> {
+        let _ = self.peek()?;
+
+        let mut lookahead = self.lookahead.take().unwrap_or_default();
+        while lookahead.len() < n {
+            let last = lookahead
+                .back()
+                .or(self.buffer.as_ref())
+                .expect("peeked token is buffered");
+            let mut peek_scanner = last.peek_scanner.clone();
+            let mut peek_cursor = last.peek_cursor;
+            let mut trivia = Vec::new();
+            let mut found = None;
+            while let Some((tok, adv)) = scan_with_cache(
+                self.token_cache.as_deref(),
+                &mut peek_scanner,
+                self.source_text,
+                peek_cursor,
+                &self.scan_count)
+            {
+                if self.filter.as_ref().map_or(false, |f| !(f)(&tok)) {
+                    // Found a filtered token.
+                    self.push_trivia(
+                        &mut trivia,
+                        &tok,
+                        Span::enclosing(peek_cursor, adv));
+                    peek_cursor = adv;
+                } else {
+                    // Found a non-filtered token.
+                    found = Some(ScannerBuffer {
+                        peek_scanner,
+                        peek_start: peek_cursor,
+                        peek_cursor: adv,
+                        token: tok,
+                    });
+                    break;
+                }
+            }
+            self.store_trivia(trivia, peek_cursor);
+            match found {
+                Some(buf) => lookahead.push_back(buf),
+                None      => break,
+            }
+        }
+        let token = match n {
+            0 => self.buffer.as_ref().map(|buf| buf.token.clone()),
+            _ => lookahead.get(n - 1).map(|buf| buf.token.clone()),
+        };
+        let terminated = self.terminator().is_some_and(|terminator| lookahead
+            .iter()
+            .take(n)
+            .any(|buf| &buf.token == terminator));
+        self.lookahead = Some(lookahead);
+
+        if terminated { None } else { token }
+    }
+
     pub fn next_if<P>(&mut self, pred: P) -> Option<Sc::Token>
         where P: FnOnce(&Sc::Token) -> bool
     {
@@ -293,25 +946,43 @@ impl<'text, Sc> Lexer<'text, Sc>
     // Advancing
     ////////////////////////////////////////////////////////////////////////////
     fn next_nonfiltered(&mut self) -> Option<Sc::Token> {
-        if self.cursor.byte >= self.source_text.len() {
+        if self.cursor.byte >= self.source_text.len()
+            || self.is_at_terminator()
+        {
             return None;
         }
+        let prior = self.checkpoint();
         if let Some(buf) = self.buffer.take() {
+            self.save_unget(prior);
             self.scanner = buf.peek_scanner;
             self.token_start = buf.peek_start;
             if self.parse_start == self.cursor {
                 self.parse_start = buf.peek_start;
             }
             self.cursor = buf.peek_cursor;
+            self.last_span = Some(
+                Span::enclosing(self.token_start, self.cursor));
+            self.buffer = self.lookahead
+                .as_mut()
+                .and_then(|lookahead| lookahead.pop_front());
             return Some(buf.token);
         }
 
         let behind = self.parse_start == self.cursor;
-        while let Some((tok, adv)) = self.scanner
-            .scan(self.source_text, self.cursor)
+        let mut trivia = Vec::new();
+        while let Some((tok, adv)) = scan_with_cache(
+            self.token_cache.as_deref(),
+            &mut self.scanner,
+            self.source_text,
+            self.cursor,
+            &self.scan_count)
         {
             if self.filter.as_ref().map_or(false, |f| !(f)(&tok)) {
                 // Found a filtered token.
+                self.push_trivia(
+                    &mut trivia,
+                    &tok,
+                    Span::enclosing(self.cursor, adv));
                 self.cursor = adv;
                 if behind && self.filter_eager {
                     self.parse_start = adv;
@@ -319,17 +990,99 @@ impl<'text, Sc> Lexer<'text, Sc>
                 }
             } else {
                 // Found a non-filtered token.
+                self.store_trivia(trivia, self.cursor);
                 if behind {
                     self.parse_start = self.token_start;
                 }
                 self.token_start = self.cursor;
                 self.cursor = adv;
+                self.last_span = Some(
+                    Span::enclosing(self.token_start, self.cursor));
+                self.save_unget(prior);
                 return Some(tok);
             }
         }
+        self.store_trivia(trivia, self.cursor);
         None
     }
 
+    /// Saves the given lexer state to be restored by `unget`.
+    fn save_unget(&mut self, prior: Checkpoint<Sc>) {
+        match self.unget.as_mut() {
+            Some(unget) => **unget = prior,
+            None        => self.unget = Some(Box::new(prior)),
+        }
+    }
+
+    /// Rewinds the lexer to the state preceding the most recently produced
+    /// (non-filtered) token, so that the next call to `next` produces it
+    /// again.
+    ///
+    /// Only a single token can be rewound. Calling `unget` again before the
+    /// next token is produced has no effect.
+    pub fn unget(&mut self) {
+        if let Some(prior) = self.unget.take() {
+            self.reset_to(*prior);
+        }
+    }
+
+    /// Returns a `Checkpoint` of the lexer's current position and scanner
+    /// state.
+    ///
+    /// This is intended for parses which try and rewind on the same lexer,
+    /// without cloning the lexer's filters and buffers.
+    #[must_use]
+    pub fn checkpoint(&self) -> Checkpoint<Sc> {
+        Checkpoint {
+            scanner: self.scanner.clone(),
+            parse_start: self.parse_start,
+            token_start: self.token_start,
+            cursor: self.cursor,
+            last_span: self.last_span,
+        }
+    }
+
+    /// Resets the lexer's position and scanner state to the given
+    /// `Checkpoint`. Any peeked tokens are discarded.
+    pub fn reset_to(&mut self, checkpoint: Checkpoint<Sc>) {
+        self.scanner = checkpoint.scanner;
+        self.parse_start = checkpoint.parse_start;
+        self.token_start = checkpoint.token_start;
+        self.cursor = checkpoint.cursor;
+        self.last_span = checkpoint.last_span;
+        self.unget = None;
+        self.buffer = None;
+        self.lookahead = None;
+        if self.filter_eager {
+            self.buffer_next();
+        }
+    }
+
+    /// Advances the lexer's position and scanner state to the given
+    /// `Checkpoint`, which should not precede the cursor. Unlike `reset_to`,
+    /// the start of the parse span is retained. Any peeked tokens are
+    /// discarded.
+    ///
+    /// This is intended for applying a saved parse result to a lexer at the
+    /// position where the parse started.
+    pub fn advance_to_checkpoint(&mut self, checkpoint: Checkpoint<Sc>) {
+        if self.parse_start == self.cursor {
+            // The parse span starts at or after the cursor, where the saved
+            // parse started.
+            self.parse_start = checkpoint.parse_start.max(self.cursor);
+        }
+        self.scanner = checkpoint.scanner;
+        self.token_start = checkpoint.token_start;
+        self.cursor = checkpoint.cursor;
+        self.last_span = checkpoint.last_span;
+        self.unget = None;
+        self.buffer = None;
+        self.lookahead = None;
+        if self.filter_eager {
+            self.buffer_next();
+        }
+    }
+
     pub fn advance_to_recover(&mut self) -> Result<Span, RecoverError> {
         if self.recover.is_none() {
             return Ok(Span::at(self.cursor));
@@ -346,7 +1099,7 @@ impl<'text, Sc> Lexer<'text, Sc>
         let mut token_found = false;
         {
             while let Some(token) = self.peek() {
-                if rec(token)? {
+                if rec(Some(token))? {
                     token_found = true;
                     break;
                 }
@@ -354,6 +1107,11 @@ impl<'text, Sc> Lexer<'text, Sc>
             }
         }
 
+        if !token_found {
+            // Allow the recover function to reset its state.
+            let _ = rec(None);
+        }
+
         if token_found {
             Ok(Span::enclosing(start_pos, self.cursor))
         } else {
@@ -394,6 +1152,66 @@ impl<'text, Sc> Lexer<'text, Sc>
     {
         IterWithSpans { lexer: self }
     }
+
+    /// Returns an iterator over the lexer tokens together with their spans and
+    /// the source text they span.
+    pub fn iter_lexemes_with_text(&mut self)
+        -> IterLexemesWithText<'text, '_, Sc>
+        where Sc: Scanner
+    {
+        IterLexemesWithText { lexer: self }
+    }
+
+    /// Returns a new lexer which scans the remaining source text from the
+    /// current cursor position using the given scanner.
+    ///
+    /// This is intended for parsing embedded text which requires a different
+    /// scanner. The returned lexer has no filter. Use `resume_from` to
+    /// continue scanning after the text consumed by the returned lexer.
+    pub fn scan_with<S>(&self, scanner: S) -> Lexer<'text, S>
+        where S: Scanner,
+    {
+        let mut lexer = Lexer::new(scanner, self.source_text);
+        lexer.parse_start = self.cursor;
+        lexer.token_start = self.cursor;
+        lexer.cursor = self.cursor;
+        lexer
+    }
+
+    /// Advances the lexer to the cursor position of the given lexer, which is
+    /// typically one returned from `scan_with`. The text between the cursor
+    /// positions is treated as a single consumed token.
+    pub fn resume_from<S>(&mut self, other: &Lexer<'text, S>)
+        where S: Scanner,
+    {
+        if other.cursor <= self.cursor { return; }
+
+        self.token_start = self.cursor;
+        self.cursor = other.cursor;
+        self.last_span = Some(Span::enclosing(self.token_start, self.cursor));
+        self.unget = None;
+        self.buffer = None;
+        self.lookahead = None;
+        if self.filter_eager {
+            self.buffer_next();
+        }
+    }
+
+    /// Returns an iterator over the tokens within the given span of the source
+    /// text, together with their spans. The tokens are scanned from the start
+    /// of the span using the given scanner and the lexer's current filter. Any
+    /// token extending past the end of the span ends the iteration.
+    pub fn span_tokens(&self, scanner: Sc, span: Span)
+        -> SpanTokens<'text, Sc>
+    {
+        let mut lexer = Lexer::new(scanner, self.source_text);
+        lexer.filter.clone_from(&self.filter);
+        lexer.filter_eager = self.filter_eager;
+        lexer.parse_start = span.start();
+        lexer.token_start = span.start();
+        lexer.cursor = span.start();
+        SpanTokens { lexer, end: span.end() }
+    }
 }
 
 #[cfg(test)]
@@ -403,11 +1221,17 @@ impl<'text, Sc> PartialEq for Lexer<'text, Sc>
     fn eq(&self, other: &Self) -> bool {
         self.scanner == other.scanner &&
         self.filter.is_some() == other.filter.is_some() &&
+        self.extras.as_ref().is_some_and(|e| e.trivia.is_some()) ==
+            other.extras.as_ref().is_some_and(|e| e.trivia.is_some()) &&
         self.recover.is_some() == other.recover.is_some() &&
+        self.terminator() == other.terminator() &&
         self.token_start == other.token_start &&
         self.parse_start == other.parse_start &&
         self.cursor == other.cursor &&
         self.buffer == other.buffer &&
+        self.lookahead.as_deref().filter(|l| !l.is_empty()) ==
+            other.lookahead.as_deref().filter(|l| !l.is_empty()) &&
+        self.last_span == other.last_span &&
         self.source_text == other.source_text
     }
 }
@@ -421,10 +1245,22 @@ impl<'text, Sc> Debug for Lexer<'text, Sc>
             .field("token_start", &self.token_start)
             .field("cursor", &self.cursor)
             .field("buffer", &self.buffer)
+            .field("lookahead", &self.lookahead)
+            .field("unget", &self.unget)
+            .field("last_span", &self.last_span)
+            .field("scan_count", &self.scan_count.get())
             .field("scanner", &self.scanner)
             .field("filter", &self.filter.is_some())
+            .field("filter_stack", &self.extras
+                .as_ref()
+                .map_or(0, |extras| extras.filter_stack.len()))
             .field("filter_eager", &self.filter_eager)
+            .field("trivia", &self.extras
+                .as_ref()
+                .is_some_and(|extras| extras.trivia.is_some()))
+            .field("trivia_buffer", &self.trivia_buffer.borrow())
             .field("recover", &self.recover.is_some())
+            .field("terminator", &self.terminator())
             .field("source_text", &self.source_text)
             .finish()
     }
@@ -493,3 +1329,109 @@ impl<'text, 'l, Sc> Iterator for IterWithSpans<'text, 'l, Sc>
             .map(|t| (t, self.lexer.token_span()))
     }
 }
+
+
+////////////////////////////////////////////////////////////////////////////////
+// IterLexemesWithText
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over lexer tokens together with their spans and source text.
+/// Created by the `Lexer::iter_lexemes_with_text` method.
+#[derive(Debug)]
+pub struct IterLexemesWithText<'text, 'l, Sc> 
+    where Sc: Scanner,
+{
+    lexer: &'l mut Lexer<'text, Sc>
+}
+
+impl<'text, 'l, Sc> Iterator for IterLexemesWithText<'text, 'l, Sc>
+    where Sc: Scanner,
+{
+    type Item = (Sc::Token, Span, &'text str);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.lexer.next()?;
+        let span = self.lexer.token_span();
+        let offset = self.lexer.source_text.start_position().byte;
+        let text: &'text str = self.lexer.source_text.text();
+        Some((
+            token,
+            span,
+            &text[span.start().byte - offset..span.end().byte - offset]))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SpanTokens
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over the tokens within a span of source text together with
+/// their spans. Created by the `Lexer::span_tokens` method.
+#[derive(Debug)]
+pub struct SpanTokens<'text, Sc> 
+    where Sc: Scanner,
+{
+    lexer: Lexer<'text, Sc>,
+    end: Pos,
+}
+
+impl<'text, Sc> Iterator for SpanTokens<'text, Sc>
+    where Sc: Scanner,
+{
+    type Item = (Sc::Token, Span);
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.lexer.cursor.byte >= self.end.byte { return None; }
+
+        let token = self.lexer.next()?;
+        let span = self.lexer.token_span();
+        if span.end().byte > self.end.byte {
+            self.lexer.cursor = self.end;
+            return None;
+        }
+        Some((token, span))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// FilterGuard
+////////////////////////////////////////////////////////////////////////////////
+/// A guard which restores a lexer's token filter when dropped. Created by the
+/// `Lexer::with_filter_scope` method.
+#[derive(Debug)]
+pub struct FilterGuard<'l, 'text, Sc>
+    where Sc: Scanner,
+{
+    lexer: &'l mut Lexer<'text, Sc>,
+    depth: usize,
+}
+
+impl<'l, 'text, Sc> Deref for FilterGuard<'l, 'text, Sc>
+    where Sc: Scanner,
+{
+    type Target = Lexer<'text, Sc>;
+
+    fn deref(&self) -> &Self::Target {
+        self.lexer
+    }
+}
+
+impl<'l, 'text, Sc> DerefMut for FilterGuard<'l, 'text, Sc>
+    where Sc: Scanner,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.lexer
+    }
+}
+
+impl<'l, 'text, Sc> Drop for FilterGuard<'l, 'text, Sc>
+    where Sc: Scanner,
+{
+    fn drop(&mut self) {
+        // Discard any filters pushed within the scope and left unpopped.
+        if let Some(extras) = self.lexer.extras.as_mut() {
+            extras.filter_stack.truncate(self.depth + 1);
+        }
+        let _ = self.lexer.pop_filter();
+    }
+}
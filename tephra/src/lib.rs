@@ -102,6 +102,7 @@
 mod context;
 mod lexer;
 mod result;
+mod stream;
 #[cfg(test)]
 mod test;
 
@@ -110,3 +111,4 @@ pub use tephra_error::*;
 pub use lexer::*;
 pub use result::*;
 pub use context::*;
+pub use stream::*;
@@ -48,6 +48,11 @@ pub trait ParseResultExt<'text, Sc, V>
     /// Applies any `ErrorTransform`s in the given `Context`.
     #[must_use]
     fn apply_context(self, ctx: Context<'text, Sc>) -> Self;
+
+    /// Applies the given function to the `ParseResult`'s error, if it failed.
+    #[must_use]
+    fn map_err_context<F>(self, f: F) -> Self
+        where F: FnOnce(Box<dyn ParseError>) -> Box<dyn ParseError>;
 }
 
 
@@ -81,6 +86,15 @@ impl<'text, Sc, V> ParseResultExt<'text, Sc, V> for ParseResult<'text, Sc, V>
             Err(fail) => Err(ctx.apply_error_transform_recursive(fail)),
         }
     }
+
+    fn map_err_context<F>(self, f: F) -> Self
+        where F: FnOnce(Box<dyn ParseError>) -> Box<dyn ParseError>
+    {
+        match self {
+            Ok(succ)  => Ok(succ),
+            Err(fail) => Err((f)(fail)),
+        }
+    }
 }
 
 
@@ -155,6 +169,9 @@ impl<'text, Sc, V> Debug for Success<'text, Sc, V>
 // Spanned
 ////////////////////////////////////////////////////////////////////////////////
 /// A parsed value with its span.
+///
+/// `PartialEq` compares both the value and the span. Use `value_eq` to compare
+/// only the values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Spanned<T> {
     /// The span of the value's source text.
@@ -174,4 +191,12 @@ impl<T> Spanned<T> {
             value: (f)(self.value),
         }
     }
+
+    /// Returns `true` if the values are equal, regardless of their spans.
+    #[must_use]
+    pub fn value_eq<U>(&self, other: &Spanned<U>) -> bool
+        where T: PartialEq<U>
+    {
+        self.value == other.value
+    }
 }
@@ -0,0 +1,293 @@
+////////////////////////////////////////////////////////////////////////////////
+// Tephra parser library
+////////////////////////////////////////////////////////////////////////////////
+// Copyright 2022 Skylor R. Schermer
+// This code is dual licenced using the MIT or Apache 2 license.
+// See licence-mit.md and licence-apache.md for details.
+////////////////////////////////////////////////////////////////////////////////
+//! Streaming lexer definitions.
+////////////////////////////////////////////////////////////////////////////////
+
+// Internal library imports.
+use crate::Scanner;
+use crate::Spanned;
+use tephra_span::ColumnMetrics;
+use tephra_span::Pos;
+use tephra_span::SourceText;
+use tephra_span::Span;
+
+// Standard library imports.
+use std::fmt::Debug;
+use std::io;
+use std::io::BufRead;
+use std::rc::Rc;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// StreamLexer
+////////////////////////////////////////////////////////////////////////////////
+/// The default number of bytes read from the stream at a time.
+const STREAM_DEFAULT_CHUNK_SIZE: usize = 4096;
+/// The default number of bytes required to follow a scanned token.
+const STREAM_DEFAULT_LOOKAHEAD: usize = 1;
+/// The default number of bytes buffered before a failed scan is abandoned.
+const STREAM_DEFAULT_MAX_TOKEN_LEN: usize = 64 * 1024;
+
+/// A lexer which scans tokens from a `BufRead` stream, reading more of the
+/// stream as needed.
+///
+/// A scanned token is only accepted once the text following it has been read,
+/// or the end of the stream has been reached. A token ending within the
+/// lookahead of the end of the buffered text, or a failed scan, causes more
+/// text to be read and the scan to be retried. This allows tokens to span chunk
+/// boundaries. A failed scan is no longer retried once the maximum token length
+/// is buffered, which bounds the text read when no token can be scanned. Text
+/// preceding the cursor is discarded once it is scanned, and all spans are
+/// relative to the start of the stream.
+///
+/// By default, a single byte following the token is required. Scanners which
+/// examine more text following a token to decide between tokens, such as `.`
+/// and `...`, may scan incorrectly at chunk boundaries unless a sufficient
+/// lookahead is set with `with_lookahead`.
+pub struct StreamLexer<R, Sc>
+    where
+        R: BufRead,
+        Sc: Scanner,
+{
+    /// The stream to read from.
+    reader: R,
+    /// The token scanner.
+    scanner: Sc,
+    /// The token filter.
+    filter: Option<Rc<dyn Fn(&Sc::Token) -> bool>>,
+    /// The buffered text, beginning at `buffer_start`.
+    buffer: String,
+    /// Bytes read from the stream which do not yet form a complete UTF-8
+    /// sequence.
+    pending: Vec<u8>,
+    /// The position of the start of the buffered text.
+    buffer_start: Pos,
+    /// The position of the end of the most recently scanned token.
+    cursor: Pos,
+    /// The column metrics for the stream text.
+    column_metrics: ColumnMetrics,
+    /// The maximum number of bytes to read from the stream at a time.
+    chunk_size: usize,
+    /// The number of bytes which must be buffered following a token before it
+    /// is accepted.
+    lookahead: usize,
+    /// The number of bytes which must be buffered following the cursor before
+    /// a failed scan is abandoned.
+    max_token_len: usize,
+    /// Whether the end of the stream has been reached.
+    exhausted: bool,
+}
+
+impl<R, Sc> StreamLexer<R, Sc>
+    where
+        R: BufRead,
+        Sc: Scanner,
+{
+    /// Constructs a new `StreamLexer` reading from the given stream.
+    #[must_use]
+    pub fn new(scanner: Sc, reader: R) -> Self {
+        StreamLexer {
+            reader,
+            scanner,
+            filter: None,
+            buffer: String::new(),
+            pending: Vec::new(),
+            buffer_start: Pos::default(),
+            cursor: Pos::default(),
+            column_metrics: ColumnMetrics::default(),
+            chunk_size: STREAM_DEFAULT_CHUNK_SIZE,
+            lookahead: STREAM_DEFAULT_LOOKAHEAD,
+            max_token_len: STREAM_DEFAULT_MAX_TOKEN_LEN,
+            exhausted: false,
+        }
+    }
+
+    /// Returns the given `StreamLexer` with the given column metrics.
+    #[must_use]
+    pub fn with_column_metrics(mut self, metrics: ColumnMetrics) -> Self {
+        self.column_metrics = metrics;
+        self
+    }
+
+    /// Returns the given `StreamLexer` with the given token filter. Tokens
+    /// for which the filter returns `false` are skipped.
+    #[must_use]
+    pub fn with_filter(mut self, filter: Option<Rc<dyn Fn(&Sc::Token) -> bool>>)
+        -> Self
+    {
+        self.filter = filter;
+        self
+    }
+
+    /// Returns the given `StreamLexer` with the given maximum number of bytes
+    /// to read from the stream at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "StreamLexer chunk size must be nonzero");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Returns the given `StreamLexer` with the given number of bytes which
+    /// must be buffered following a scanned token before it is accepted. This
+    /// should be at least the number of bytes the scanner examines past the
+    /// end of any token.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lookahead` is zero.
+    #[must_use]
+    pub fn with_lookahead(mut self, lookahead: usize) -> Self {
+        assert!(lookahead > 0, "StreamLexer lookahead must be nonzero");
+        self.lookahead = lookahead;
+        self
+    }
+
+    /// Returns the given `StreamLexer` with the given maximum token length in
+    /// bytes. If a scan fails with at least this many bytes buffered following
+    /// the cursor, no more text is read and no further tokens are scanned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_token_len` is zero.
+    #[must_use]
+    pub fn with_max_token_len(mut self, max_token_len: usize) -> Self {
+        assert!(max_token_len > 0,
+            "StreamLexer maximum token length must be nonzero");
+        self.max_token_len = max_token_len;
+        self
+    }
+
+    /// Returns the position of the end of the most recently scanned token.
+    #[must_use]
+    pub fn cursor_pos(&self) -> Pos {
+        self.cursor
+    }
+
+    /// Scans and returns the next non-filtered token with its span, or `None`
+    /// if the end of the stream is reached or no further tokens can be
+    /// scanned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the stream fails or the stream text
+    /// is not valid UTF-8.
+    pub fn next_spanned(&mut self) -> io::Result<Option<Spanned<Sc::Token>>> {
+        loop {
+            let buffer_end = self.buffer_start.byte + self.buffer.len();
+            let mut scanner = self.scanner.clone();
+            let next = if self.cursor.byte < buffer_end {
+                let source = SourceText::new(self.buffer.as_str())
+                    .with_column_metrics(self.column_metrics)
+                    .with_start_position(self.buffer_start);
+                scanner.scan(source, self.cursor)
+            } else {
+                None
+            };
+
+            // A token ending within the lookahead of the end of the buffer may
+            // continue into the unread text, so it is rescanned once more text
+            // is available. A failed scan is rescanned until the maximum token
+            // length is buffered.
+            let complete = match &next {
+                Some((_, adv)) => adv.byte + self.lookahead <= buffer_end
+                    || self.exhausted,
+                None           => self.exhausted
+                    || buffer_end - self.cursor.byte >= self.max_token_len,
+            };
+            if !complete {
+                self.read_chunk()?;
+                continue;
+            }
+
+            let Some((token, adv)) = next else { return Ok(None); };
+            let span = Span::enclosing(self.cursor, adv);
+            self.scanner = scanner;
+            self.cursor = adv;
+            self.discard_scanned();
+
+            if self.filter.as_ref().is_none_or(|f| (f)(&token)) {
+                return Ok(Some(Spanned { value: token, span }));
+            }
+        }
+    }
+
+    /// Reads the next chunk of the stream into the buffer.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let available = self.reader.fill_buf()?;
+        if available.is_empty() {
+            self.exhausted = true;
+            if !self.pending.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream ended within a UTF-8 sequence"));
+            }
+            return Ok(());
+        }
+
+        let count = std::cmp::min(available.len(), self.chunk_size);
+        self.pending.extend_from_slice(&available[..count]);
+        self.reader.consume(count);
+
+        // Retain any incomplete UTF-8 sequence until the rest is read.
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+        self.buffer.push_str(std::str::from_utf8(&self.pending[..valid])
+            .expect("valid UTF-8 prefix"));
+        let _ = self.pending.drain(..valid);
+        Ok(())
+    }
+
+    /// Discards the buffered text preceding the cursor.
+    fn discard_scanned(&mut self) {
+        let scanned = self.cursor.byte - self.buffer_start.byte;
+        let _ = self.buffer.drain(..scanned);
+        self.buffer_start = self.cursor;
+    }
+}
+
+impl<R, Sc> Debug for StreamLexer<R, Sc>
+    where
+        R: BufRead,
+        Sc: Scanner,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamLexer")
+            .field("scanner", &self.scanner)
+            .field("filter_set", &self.filter.is_some())
+            .field("buffer", &self.buffer)
+            .field("pending", &self.pending)
+            .field("buffer_start", &self.buffer_start)
+            .field("cursor", &self.cursor)
+            .field("column_metrics", &self.column_metrics)
+            .field("chunk_size", &self.chunk_size)
+            .field("lookahead", &self.lookahead)
+            .field("max_token_len", &self.max_token_len)
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, Sc> Iterator for StreamLexer<R, Sc>
+    where
+        R: BufRead,
+        Sc: Scanner,
+{
+    type Item = io::Result<Spanned<Sc::Token>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_spanned().transpose()
+    }
+}
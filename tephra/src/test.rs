@@ -10,10 +10,17 @@
 
 
 use crate::Lexer;
+use crate::ModalScanner;
 use crate::Pos;
+use crate::ScanMode;
+use crate::scan_char_table;
+use crate::scan_longest_match;
+use crate::SourceText;
+use crate::StreamLexer;
 use crate::SourceTextRef;
 use crate::SourceTextOwned;
 use crate::Scanner;
+use crate::Spanned;
 use crate::Success;
 use crate::ParseResult;
 use tephra_span::Span;
@@ -43,13 +50,352 @@ impl Scanner for Void {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Modal Scanners
+////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModeToken {
+    Word,
+    Expr,
+    Colon,
+    Semicolon,
+    Quote,
+}
+
+impl std::fmt::Display for ModeToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Scans a `:` or `;` token, or a run of alphanumeric characters as the given
+/// token.
+fn scan_mode_token(source: SourceTextRef<'_>, base: Pos, word: ModeToken)
+    -> Option<(ModeToken, Pos)>
+{
+    scan_char_table(source, base, &[
+            (':', ModeToken::Colon),
+            (';', ModeToken::Semicolon),
+        ])
+        .or_else(|| source
+            .position_after_chars_matching(base, char::is_alphanumeric)
+            .map(|adv| (word, adv)))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct CommandScanner;
+
+impl Scanner for CommandScanner {
+    type Token = ModeToken;
+
+    fn scan(&mut self, source: SourceTextRef<'_>, base: Pos)
+        -> Option<(Self::Token, Pos)>
+    {
+        scan_mode_token(source, base, ModeToken::Word)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ExpressionScanner;
+
+impl Scanner for ExpressionScanner {
+    type Token = ModeToken;
+
+    fn scan(&mut self, source: SourceTextRef<'_>, base: Pos)
+        -> Option<(Self::Token, Pos)>
+    {
+        scan_mode_token(source, base, ModeToken::Expr)
+    }
+}
+
+/// Scans words and `"` tokens, tracking whether a quoted string is open.
+#[derive(Debug, Clone, PartialEq)]
+struct QuoteScanner {
+    open: bool,
+}
+
+impl Scanner for QuoteScanner {
+    type Token = ModeToken;
+
+    fn scan(&mut self, source: SourceTextRef<'_>, base: Pos)
+        -> Option<(Self::Token, Pos)>
+    {
+        let scanned = scan_char_table(source, base, &[('"', ModeToken::Quote)])
+            .or_else(|| scan_mode_token(source, base, ModeToken::Word));
+        if let Some((ModeToken::Quote, _)) = scanned {
+            self.open = !self.open;
+        }
+        scanned
+    }
+
+    fn is_safe_restart_point(&self, _source: SourceTextRef<'_>, _pos: Pos)
+        -> bool
+    {
+        !self.open
+    }
+}
+
+#[test]
+fn modal_scanner_switch_on_colon() {
+    use ModeToken::*;
+    let source = SourceText::new("ab:cd;ef:gh");
+    let scanner = ModalScanner::new(
+        CommandScanner,
+        ExpressionScanner,
+        |tok| match tok {
+            Colon     => Some(ScanMode::Secondary),
+            Semicolon => Some(ScanMode::Primary),
+            _         => None,
+        });
+    let lexer = Lexer::new(scanner, source);
+
+    let actual = lexer.collect::<Vec<_>>();
+    let expected = vec![Word, Colon, Expr, Semicolon, Word, Colon, Expr];
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn scan_longest_match_prefers_longest() {
+    use ModeToken::*;
+    let source = SourceText::new("ab:cd;ef");
+    let word = |source: SourceTextRef<'_>, base: Pos| source
+        .position_after_chars_matching(base, char::is_alphanumeric)
+        .map(|adv| (Word, adv));
+    let expr = |source: SourceTextRef<'_>, base: Pos| source
+        .position_after_chars_matching(base,
+            |c| c.is_alphanumeric() || c == ':')
+        .map(|adv| (Expr, adv));
+
+    assert_eq!(
+        scan_longest_match(source, Pos::new(0, 0, 0), &[&word, &expr]),
+        Some((Expr, Pos::new(5, 0, 5))));
+    assert_eq!(
+        scan_longest_match(source, Pos::new(6, 0, 6), &[&word, &expr]),
+        Some((Word, Pos::new(8, 0, 8))));
+    assert_eq!(
+        scan_longest_match(source, Pos::new(5, 0, 5), &[&word, &expr]),
+        None);
+}
+
+#[test]
+fn spanned_value_eq() {
+    let a = Spanned {
+        span: Span::enclosing(Pos::new(0, 0, 0), Pos::new(2, 0, 2)),
+        value: "ab",
+    };
+    let b = Spanned {
+        span: Span::enclosing(Pos::new(3, 0, 3), Pos::new(5, 0, 5)),
+        value: "ab",
+    };
+
+    assert!(a.value_eq(&b));
+    assert_ne!(a, b);
+    assert!(!a.value_eq(&Spanned { span: a.span, value: "cd" }));
+}
+
+#[test]
+fn lexer_scan_with_resume() {
+    use ModeToken::*;
+    let source = SourceText::new("ab:cd;ef");
+    let mut lexer = Lexer::new(CommandScanner, source);
+
+    assert_eq!(lexer.next(), Some(Word));
+    assert_eq!(lexer.next(), Some(Colon));
+
+    let mut embedded = lexer.scan_with(ExpressionScanner);
+    assert_eq!(embedded.next(), Some(Expr));
+    assert_eq!(embedded.cursor_pos(), Pos::new(5, 0, 5));
+
+    lexer.resume_from(&embedded);
+    assert_eq!(lexer.cursor_pos(), Pos::new(5, 0, 5));
+    assert_eq!(lexer.next(), Some(Semicolon));
+    assert_eq!(lexer.next(), Some(Word));
+    assert_eq!(lexer.next(), None);
+}
+
+#[test]
+fn lexer_reset_to_checkpoint() {
+    use ModeToken::*;
+    let source = SourceText::new("ab:cd;ef");
+    let scanner = ModalScanner::new(
+        CommandScanner,
+        ExpressionScanner,
+        |tok| match tok {
+            Colon     => Some(ScanMode::Secondary),
+            Semicolon => Some(ScanMode::Primary),
+            _         => None,
+        });
+    let mut lexer = Lexer::new(scanner, source);
+
+    assert_eq!(lexer.next(), Some(Word));
+    assert_eq!(lexer.next(), Some(Colon));
+    let checkpoint = lexer.checkpoint();
+    assert_eq!(checkpoint.cursor_pos(), Pos::new(3, 0, 3));
+
+    assert_eq!(lexer.next(), Some(Expr));
+    assert_eq!(lexer.next(), Some(Semicolon));
+    assert_eq!(lexer.next(), Some(Word));
+    assert_eq!(lexer.next(), None);
+
+    // The scanner mode is restored along with the cursor.
+    lexer.reset_to(checkpoint);
+    assert_eq!(lexer.cursor_pos(), Pos::new(3, 0, 3));
+    assert_eq!(lexer.collect::<Vec<_>>(), vec![Expr, Semicolon, Word]);
+}
+
+#[test]
+fn scanner_safe_restart_point() {
+    let source = SourceText::new("ab\"cd\"ef");
+    let mut scanner = QuoteScanner { open: false };
+    let mut pos = Pos::new(0, 0, 0);
+
+    let mut actual = Vec::new();
+    while let Some((_, next)) = scanner.scan(source, pos) {
+        actual.push((next.byte, scanner.is_safe_restart_point(source, next)));
+        pos = next;
+    }
+    let expected = vec![(2, true), (3, false), (5, false), (6, true), (8, true)];
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn modal_scanner_safe_restart_point() {
+    use ModeToken::*;
+    let source = SourceText::new("ab:\"cd");
+    let mut scanner = ModalScanner::new(
+        CommandScanner,
+        QuoteScanner { open: false },
+        |tok| match tok {
+            Colon     => Some(ScanMode::Secondary),
+            Semicolon => Some(ScanMode::Primary),
+            _         => None,
+        });
+    let mut pos = Pos::new(0, 0, 0);
+
+    let mut actual = Vec::new();
+    while let Some((tok, next)) = scanner.scan(source, pos) {
+        actual.push((tok, scanner.is_safe_restart_point(source, next)));
+        pos = next;
+    }
+    let expected = vec![
+        (Word, true),
+        (Colon, true),
+        (Quote, false),
+        (Word, false),
+    ];
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn stream_lexer_small_chunks() {
+    const TEXT: &str = "ab:cdé;éf:gh;ij";
+    let mut lexer = Lexer::new(CommandScanner, SourceText::new(TEXT));
+    let expected = lexer
+        .iter_with_spans()
+        .collect::<Vec<_>>();
+
+    for chunk_size in 1..=5 {
+        let actual = StreamLexer::new(
+                CommandScanner,
+                std::io::Cursor::new(TEXT.as_bytes()))
+            .with_chunk_size(chunk_size)
+            .map(|res| res
+                .map(|spanned| (spanned.value, spanned.span))
+                .expect("valid stream"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected, "chunk size {chunk_size}");
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DotToken {
+    Word,
+    Dot,
+    Ellipsis,
+}
+
+impl std::fmt::Display for DotToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Scans a `...` or `.` token, or a run of alphanumeric characters.
+#[derive(Debug, Clone, PartialEq)]
+struct DotScanner;
+
+impl Scanner for DotScanner {
+    type Token = DotToken;
+
+    fn scan(&mut self, source: SourceTextRef<'_>, base: Pos)
+        -> Option<(Self::Token, Pos)>
+    {
+        source
+            .position_after_str(base, "...")
+            .map(|adv| (DotToken::Ellipsis, adv))
+            .or_else(|| source
+                .position_after_str(base, ".")
+                .map(|adv| (DotToken::Dot, adv)))
+            .or_else(|| source
+                .position_after_chars_matching(base, char::is_alphanumeric)
+                .map(|adv| (DotToken::Word, adv)))
+    }
+}
+
+#[test]
+fn stream_lexer_lookahead() {
+    const TEXT: &str = "ab...cd.ef...";
+    let mut lexer = Lexer::new(DotScanner, SourceText::new(TEXT));
+    let expected = lexer
+        .iter_with_spans()
+        .collect::<Vec<_>>();
+
+    for chunk_size in 1..=5 {
+        let actual = StreamLexer::new(
+                DotScanner,
+                std::io::Cursor::new(TEXT.as_bytes()))
+            .with_chunk_size(chunk_size)
+            .with_lookahead(2)
+            .map(|res| res
+                .map(|spanned| (spanned.value, spanned.span))
+                .expect("valid stream"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected, "chunk size {chunk_size}");
+    }
+}
+
+#[test]
+fn stream_lexer_max_token_len() {
+    use std::io::Read as _;
+
+    let reader = std::io::BufReader::new(
+        b"ab".chain(std::io::repeat(b'#')));
+    let actual = StreamLexer::new(DotScanner, reader)
+        .with_chunk_size(4)
+        .with_max_token_len(16)
+        .map(|res| res
+            .map(|spanned| (spanned.value, spanned.span))
+            .expect("valid stream"))
+        .collect::<Vec<_>>();
+
+    let expected = vec![
+        (DotToken::Word, Span::enclosing(Pos::new(0, 0, 0), Pos::new(2, 0, 2))),
+    ];
+    assert_eq!(actual, expected);
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Object size canaries.
 ////////////////////////////////////////////////////////////////////////////////
 
 #[test]
 fn verify_lexer_size() {
-    assert_eq!(std::mem::size_of::<Lexer<'_, Void>>(), 232);
+    assert_eq!(std::mem::size_of::<Lexer<'_, Void>>(), 336);
 }
 
 #[test]
@@ -64,12 +410,12 @@ fn verify_span_size() {
 
 #[test]
 fn verify_success_size() {
-    assert_eq!(std::mem::size_of::<Success<'_, Void, ()>>(), 232);
+    assert_eq!(std::mem::size_of::<Success<'_, Void, ()>>(), 336);
 }
 
 #[test]
 fn verify_result_size() {
-    assert_eq!(std::mem::size_of::<ParseResult<'_, Void, ()>>(), 232);
+    assert_eq!(std::mem::size_of::<ParseResult<'_, Void, ()>>(), 336);
 }
 
 #[test]
@@ -79,7 +425,7 @@ fn verify_line_ending_size() {
 
 #[test]
 fn verify_column_metrics_size() {
-    assert_eq!(std::mem::size_of::<ColumnMetrics>(), 2);
+    assert_eq!(std::mem::size_of::<ColumnMetrics>(), 3);
 }
 
 #[test]